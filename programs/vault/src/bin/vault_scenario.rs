@@ -0,0 +1,44 @@
+//! Replays scenario files and reports the steps that did not go as expected
+//!
+//! Usage: `vault-scenario <scenario.json>...`
+
+use std::{env, fs, process::ExitCode};
+
+use certora_vault::scenario::Scenario;
+
+fn run(path: &str) -> Result<bool, String> {
+    let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let scenario = Scenario::from_json(&json).map_err(|e| e.to_string())?;
+    let mismatches = scenario.run()?;
+
+    for mismatch in &mismatches {
+        println!("{path}: {mismatch}");
+    }
+    Ok(mismatches.is_empty())
+}
+
+fn main() -> ExitCode {
+    let paths: Vec<String> = env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: vault-scenario <scenario.json>...");
+        return ExitCode::FAILURE;
+    }
+
+    let mut passed = true;
+    for path in &paths {
+        match run(path) {
+            Ok(true) => println!("{path}: ok"),
+            Ok(false) => passed = false,
+            Err(e) => {
+                eprintln!("{path}: {e}");
+                passed = false;
+            }
+        }
+    }
+
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
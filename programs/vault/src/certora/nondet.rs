@@ -1,12 +1,17 @@
 use crate::certora::constants::MAX_FEE_BPS;
 /// Implementations for cvlr::nondet::Nondet trait
-use crate::state::Vault;
+use crate::state::{
+    AuthorityRole, ExitFeeTier, RoleEntry, UserHolding, Vault, VaultStatus, MAX_DECIMALS_OFFSET,
+    VAULT_DISCRIMINATOR, VAULT_VERSION,
+};
 use cvlr::nondet::{nondet, nondet_with};
 use cvlr_solana::cvlr_nondet_pubkey;
 
 impl cvlr::nondet::Nondet for Vault {
     fn nondet() -> Self {
         Self {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
             admin: cvlr_nondet_pubkey(),
             slash_admin: cvlr_nondet_pubkey(),
             shares_mint: cvlr_nondet_pubkey(),
@@ -15,9 +20,78 @@ impl cvlr::nondet::Nondet for Vault {
             assets: u64::nondet().into(),
             vault_assets_account: cvlr_nondet_pubkey(),
             vault_assets_account_bump: nondet(),
+            shares_mint_authority_bump: nondet(),
+            decimals_offset: nondet_with(|x: &u8| *x <= MAX_DECIMALS_OFFSET),
+            management_fee_bps: nondet_with(|x: &u64| *x <= MAX_FEE_BPS).into(),
+            last_fee_accrual_ts: i64::nondet().into(),
+            performance_fee_bps: nondet_with(|x: &u64| *x <= MAX_FEE_BPS).into(),
+            high_water_mark_assets: u64::nondet().into(),
+            high_water_mark_shares: nondet_with(|x: &u64| *x > 0).into(),
+            exit_fee_tiers: core::array::from_fn(|_| nondet()),
+            exit_fee_holding_period: i64::nondet().into(),
+            exit_fee_held_bps: nondet_with(|x: &u64| *x <= MAX_FEE_BPS).into(),
+            pending_admin: cvlr_nondet_pubkey(),
+            pending_slash_admin: cvlr_nondet_pubkey(),
+            roles: core::array::from_fn(|_| nondet()),
+            status: nondet_with(|x: &u8| VaultStatus::try_from(*x).is_ok()),
             fee_bps: nondet_with(|x: &u64| *x <= MAX_FEE_BPS).into(),
             fee_amount: u64::nondet().into(),
             fee_token_account: cvlr_nondet_pubkey(),
         }
     }
 }
+
+impl cvlr::nondet::Nondet for ExitFeeTier {
+    fn nondet() -> Self {
+        Self {
+            min_assets: u64::nondet().into(),
+            fee_bps: nondet_with(|x: &u64| *x <= MAX_FEE_BPS).into(),
+        }
+    }
+}
+
+impl cvlr::nondet::Nondet for RoleEntry {
+    fn nondet() -> Self {
+        Self {
+            authority: cvlr_nondet_pubkey(),
+            roles: nondet(),
+        }
+    }
+}
+
+impl cvlr::nondet::Nondet for UserHolding {
+    fn nondet() -> Self {
+        Self {
+            held_for: nondet(),
+            shares: nondet(),
+        }
+    }
+}
+
+/// A redeemer with or without a user position
+pub fn nondet_user_holding() -> Option<UserHolding> {
+    if nondet() {
+        Some(nondet())
+    } else {
+        None
+    }
+}
+
+pub fn nondet_authority_role() -> AuthorityRole {
+    if nondet() {
+        AuthorityRole::Admin
+    } else {
+        AuthorityRole::SlashAdmin
+    }
+}
+
+/// A status the pauser can set, every status but [`VaultStatus::WindDown`]
+pub fn nondet_pause_status() -> VaultStatus {
+    if nondet() {
+        VaultStatus::Active
+    } else if nondet() {
+        VaultStatus::DepositsPaused
+    } else {
+        VaultStatus::FullyPaused
+    }
+}
@@ -1,11 +1,173 @@
+use crate::certora::mocks::processor::CvlrTokenProgram;
+use crate::certora::nondet::{nondet_authority_role, nondet_pause_status};
 use crate::certora::specs::{
-    access_control::props::AccessControlProcessDeposit, base_processor::base_process_deposit,
+    access_control::props::{
+        AccessControlAuthority, AccessControlProcessDeposit, AccessControlRole,
+    },
+    base_processor::base_process_deposit,
 };
+use crate::instruction::{ProposeAuthorityArgs, QueueParamChangeArgs, SetRolesArgs};
+use crate::processor::{
+    process_accept_authority, process_cancel_authority, process_cancel_param_change,
+    process_collect_fee, process_migrate_vault, process_propose_authority,
+    process_queue_param_change, process_set_roles, process_set_status, process_slash,
+    process_update_reward,
+};
+use crate::state::{ParamChange, Role, VaultStatus};
 use cvlr::prelude::*;
-use cvlr_solana::cvlr_deserialize_nondet_accounts;
+use cvlr_solana::{cvlr_deserialize_nondet_accounts, cvlr_nondet_pubkey};
+use solana_program::pubkey::Pubkey;
 
 #[rule]
 pub fn rule_access_control_process_deposit() {
     let accs = cvlr_deserialize_nondet_accounts();
     base_process_deposit::<AccessControlProcessDeposit>(&accs);
 }
+
+#[rule]
+pub fn rule_access_control_propose_authority() {
+    let accs = cvlr_deserialize_nondet_accounts();
+    let role = nondet_authority_role();
+    let args = ProposeAuthorityArgs {
+        role: role as u8,
+        new_authority: cvlr_nondet_pubkey(),
+    };
+
+    let pre = AccessControlAuthority::new(&accs[0], &accs[1], role);
+    process_propose_authority(&accs, &args).unwrap();
+    let post = AccessControlAuthority::new(&accs[0], &accs[1], role);
+    clog!(pre, post);
+
+    // -- only the current holder can propose
+    cvlr_assert!(pre.is_signer);
+    cvlr_assert!(pre.signer_key == pre.holder);
+    cvlr_assert!(post.holder == pre.holder);
+    cvlr_assert!(post.pending == args.new_authority);
+}
+
+#[rule]
+pub fn rule_access_control_accept_authority() {
+    let accs = cvlr_deserialize_nondet_accounts();
+    let role = nondet_authority_role();
+
+    let pre = AccessControlAuthority::new(&accs[0], &accs[1], role);
+    process_accept_authority(&accs, role).unwrap();
+    let post = AccessControlAuthority::new(&accs[0], &accs[1], role);
+    clog!(pre, post);
+
+    // -- only the pending key can complete the rotation
+    cvlr_assert!(pre.is_signer);
+    cvlr_assert!(pre.pending != Pubkey::default());
+    cvlr_assert!(pre.signer_key == pre.pending);
+    cvlr_assert!(post.holder == pre.pending);
+    cvlr_assert!(post.pending == Pubkey::default());
+}
+
+#[rule]
+pub fn rule_access_control_cancel_authority() {
+    let accs = cvlr_deserialize_nondet_accounts();
+    let role = nondet_authority_role();
+
+    let pre = AccessControlAuthority::new(&accs[0], &accs[1], role);
+    process_cancel_authority(&accs, role).unwrap();
+    let post = AccessControlAuthority::new(&accs[0], &accs[1], role);
+    clog!(pre, post);
+
+    // -- only the current holder can cancel
+    cvlr_assert!(pre.is_signer);
+    cvlr_assert!(pre.signer_key == pre.holder);
+    cvlr_assert!(post.holder == pre.holder);
+    cvlr_assert!(post.pending == Pubkey::default());
+}
+
+/// Generates a rule checking that a privileged instruction only succeeds
+/// when the account at index `$authority` signs and holds `$role`
+macro_rules! access_control_role_rule {
+    ($rule:ident, $role:expr, $authority:expr, |$accs:ident| $process:expr) => {
+        #[rule]
+        pub fn $rule() {
+            let $accs = cvlr_deserialize_nondet_accounts();
+            let pre = AccessControlRole::new(&$accs[0], &$accs[$authority], $role);
+            $process.unwrap();
+            clog!(pre);
+            cvlr_assert!(pre.is_signer);
+            cvlr_assert!(pre.has_role);
+        }
+    };
+}
+
+access_control_role_rule!(
+    rule_access_control_role_process_slash,
+    Role::Slasher,
+    4,
+    |accs| process_slash(&CvlrTokenProgram, &accs, nondet())
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_collect_fee,
+    Role::FeeManager,
+    4,
+    |accs| process_collect_fee(&CvlrTokenProgram, &accs)
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_update_reward,
+    Role::RewardUpdater,
+    2,
+    |accs| process_update_reward(&CvlrTokenProgram, &accs)
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_queue_param_change,
+    Role::Admin,
+    2,
+    |accs| {
+        let (kind, value) = ParamChange::FeeBps(nondet()).encode();
+        let args = QueueParamChangeArgs {
+            kind: kind as u8,
+            value,
+        };
+        process_queue_param_change(&accs, &args)
+    }
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_cancel_param_change,
+    Role::Admin,
+    2,
+    |accs| process_cancel_param_change(&accs)
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_set_roles,
+    Role::Admin,
+    1,
+    |accs| {
+        let args = SetRolesArgs {
+            authority: cvlr_nondet_pubkey(),
+            roles: nondet(),
+        };
+        process_set_roles(&accs, &args)
+    }
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_migrate_vault,
+    Role::Admin,
+    1,
+    |accs| process_migrate_vault(&accs)
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_set_status_pause,
+    Role::Pauser,
+    1,
+    |accs| process_set_status(&accs, nondet_pause_status())
+);
+
+access_control_role_rule!(
+    rule_access_control_role_process_set_status_wind_down,
+    Role::Admin,
+    1,
+    |accs| process_set_status(&accs, VaultStatus::WindDown)
+);
@@ -1,5 +1,5 @@
 use crate::certora::specs::base_processor::CvlrProp;
-use crate::state::Vault;
+use crate::state::{AuthorityRole, Role, Vault};
 use cvlr::cvlr_assert;
 use cvlr_solana::pubkey::Pk;
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
@@ -87,3 +87,78 @@ impl CvlrProp for AccessControlProcessDeposit {
         cvlr_assert!(self.vault_shares_mint_key == self.shares_mint_key);
     }
 }
+
+/// Holder and pending key of a rotated role, with the account signing the
+/// rotation instruction
+pub struct AccessControlAuthority {
+    pub holder: Pubkey,
+    pub pending: Pubkey,
+    pub signer_key: Pubkey,
+    pub is_signer: bool,
+}
+
+mod authority_log {
+    use super::*;
+    use cvlr::log::cvlr_log_with;
+    use cvlr::log::CvlrLog;
+
+    impl CvlrLog for AccessControlAuthority {
+        #[inline(always)]
+        fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+            logger.log_scope_start(tag);
+            cvlr_log_with("holder", &Pk(&self.holder), logger);
+            cvlr_log_with("pending", &Pk(&self.pending), logger);
+            cvlr_log_with("signer_key", &Pk(&self.signer_key), logger);
+            cvlr_log_with("is_signer", &self.is_signer, logger);
+            logger.log_scope_end(tag);
+        }
+    }
+}
+
+impl AccessControlAuthority {
+    pub fn new(vault_info: &AccountInfo, authority: &AccountInfo, role: AuthorityRole) -> Self {
+        let data = vault_info.try_borrow_data().unwrap();
+        let vault = bytemuck::from_bytes::<Vault>(&data[0..size_of::<Vault>()]);
+
+        Self {
+            holder: *vault.authority(role),
+            pending: vault.pending_authority(role).copied().unwrap_or_default(),
+            signer_key: *authority.key,
+            is_signer: authority.is_signer,
+        }
+    }
+}
+
+/// Whether the account signing a privileged instruction holds its role
+pub struct AccessControlRole {
+    pub has_role: bool,
+    pub is_signer: bool,
+}
+
+mod role_log {
+    use super::*;
+    use cvlr::log::cvlr_log_with;
+    use cvlr::log::CvlrLog;
+
+    impl CvlrLog for AccessControlRole {
+        #[inline(always)]
+        fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+            logger.log_scope_start(tag);
+            cvlr_log_with("has_role", &self.has_role, logger);
+            cvlr_log_with("is_signer", &self.is_signer, logger);
+            logger.log_scope_end(tag);
+        }
+    }
+}
+
+impl AccessControlRole {
+    pub fn new(vault_info: &AccountInfo, authority: &AccountInfo, role: Role) -> Self {
+        let data = vault_info.try_borrow_data().unwrap();
+        let vault = bytemuck::from_bytes::<Vault>(&data[0..size_of::<Vault>()]);
+
+        Self {
+            has_role: vault.has_role(authority.key, role),
+            is_signer: authority.is_signer,
+        }
+    }
+}
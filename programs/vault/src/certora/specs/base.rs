@@ -1,3 +1,4 @@
+use crate::certora::nondet::nondet_user_holding;
 use crate::operations::*;
 use crate::state::Vault;
 use cvlr::clog;
@@ -63,7 +64,7 @@ pub fn base_redeem_shares<C: CvlrProp>() {
     pre.assume_pre();
 
     let shares_amount = nondet();
-    let effect = vault_redeem_shares(&mut vault, shares_amount).unwrap();
+    let effect = vault_redeem_shares(&mut vault, shares_amount, nondet_user_holding()).unwrap();
 
     let post = C::new(&vault);
     clog!(pre, post);
@@ -76,6 +77,46 @@ pub fn base_redeem_shares<C: CvlrProp>() {
     );
 }
 
+#[inline(always)]
+pub fn base_mint_shares_exact<C: CvlrProp>() {
+    let mut vault: Vault = nondet();
+    let pre = C::new(&vault);
+    pre.assume_pre();
+
+    let shares_amount = nondet();
+    let effect = vault_mint_shares_exact(&mut vault, shares_amount).unwrap();
+
+    let post = C::new(&vault);
+    clog!(pre, post);
+    post.check_post(
+        &pre,
+        OperationParams {
+            amount: shares_amount,
+        },
+        effect,
+    );
+}
+
+#[inline(always)]
+pub fn base_withdraw_assets<C: CvlrProp>() {
+    let mut vault: Vault = nondet();
+    let pre = C::new(&vault);
+    pre.assume_pre();
+
+    let token_amount = nondet();
+    let effect = vault_withdraw_assets(&mut vault, token_amount, nondet_user_holding()).unwrap();
+
+    let post = C::new(&vault);
+    clog!(pre, post);
+    post.check_post(
+        &pre,
+        OperationParams {
+            amount: token_amount,
+        },
+        effect,
+    );
+}
+
 #[inline(always)]
 pub fn base_update_reward<C: CvlrProp>() {
     let mut vault: Vault = nondet();
@@ -115,3 +156,70 @@ pub fn base_process_slash<C: CvlrProp>() {
         effect,
     );
 }
+
+#[inline(always)]
+pub fn base_collect_fee<C: CvlrProp>() {
+    let mut vault: Vault = nondet();
+    let pre = C::new(&vault);
+    pre.assume_pre();
+
+    let effect = vault_collect_fee(&mut vault).unwrap();
+
+    let post = C::new(&vault);
+    clog!(pre, post);
+    post.check_post(&pre, OperationParams { amount: 0 }, effect);
+}
+
+#[inline(always)]
+pub fn base_deposit_assets_exact<C: CvlrProp>() {
+    let mut vault: Vault = nondet();
+    let pre = C::new(&vault);
+    pre.assume_pre();
+
+    let token_amount = nondet();
+    let effect = vault_deposit_assets_exact(&mut vault, token_amount).unwrap();
+
+    let post = C::new(&vault);
+    clog!(pre, post);
+    post.check_post(
+        &pre,
+        OperationParams {
+            amount: token_amount,
+        },
+        effect,
+    );
+}
+
+#[inline(always)]
+pub fn base_deposit_assets_with_fee_exact<C: CvlrProp>() {
+    let mut vault: Vault = nondet();
+    let pre = C::new(&vault);
+    pre.assume_pre();
+
+    let token_amount = nondet();
+    let effect = vault_deposit_assets_with_fee_exact(&mut vault, token_amount).unwrap();
+
+    let post = C::new(&vault);
+    clog!(pre, post);
+    post.check_post(
+        &pre,
+        OperationParams {
+            amount: token_amount,
+        },
+        effect,
+    );
+}
+
+#[inline(always)]
+pub fn base_accrue_management_fee<C: CvlrProp>() {
+    let mut vault: Vault = nondet();
+    let pre = C::new(&vault);
+    pre.assume_pre();
+
+    let now = nondet();
+    let effect = vault_accrue_management_fee(&mut vault, now).unwrap();
+
+    let post = C::new(&vault);
+    clog!(pre, post);
+    post.check_post(&pre, OperationParams { amount: 0 }, effect);
+}
@@ -1,10 +1,16 @@
+use crate::certora::mocks::processor::CvlrTokenProgram;
+use crate::loaders::{
+    CollectFeeAccounts, DepositAccounts, RedeemSharesAccounts, SlashAccounts, UpdateRewardAccounts,
+};
+use crate::operations::Slippage;
 use crate::processor::{
-    process_deposit, process_redeem_shares, process_slash, process_update_reward,
+    process_collect_fee, process_deposit, process_redeem_shares, process_slash,
+    process_update_reward,
 };
 use cvlr::clog;
 use cvlr::log::CvlrLog;
 use cvlr::nondet;
-use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::account_info::AccountInfo;
 
 pub trait CvlrProp: CvlrLog {
     fn new(
@@ -22,14 +28,16 @@ pub trait CvlrProp: CvlrLog {
 
 #[inline(always)]
 pub fn base_process_deposit<C: CvlrProp>(accounts: &[AccountInfo]) {
-    let iter = &mut accounts.iter();
-    let vault_info = next_account_info(iter).unwrap();
-    let vault_assets_account = next_account_info(iter).unwrap();
-    let assets_mint = next_account_info(iter).unwrap();
-    let shares_mint = next_account_info(iter).unwrap();
-    let user_assets_account = next_account_info(iter).unwrap();
-    let authority = next_account_info(iter).unwrap();
-    let user_shares_account = next_account_info(iter).unwrap();
+    let DepositAccounts {
+        vault_info,
+        vault_assets_account,
+        assets_mint,
+        shares_mint,
+        user_assets_account,
+        authority,
+        user_shares_account,
+        ..
+    } = DepositAccounts::unpack(accounts).unwrap();
 
     let pre = C::new(
         vault_info,
@@ -43,7 +51,7 @@ pub fn base_process_deposit<C: CvlrProp>(accounts: &[AccountInfo]) {
     pre.assume_pre();
 
     let amount = nondet();
-    process_deposit(accounts, amount).unwrap();
+    process_deposit(&CvlrTokenProgram, accounts, amount, Slippage::default()).unwrap();
 
     let post = C::new(
         vault_info,
@@ -61,14 +69,16 @@ pub fn base_process_deposit<C: CvlrProp>(accounts: &[AccountInfo]) {
 
 #[inline(always)]
 pub fn base_process_redeem_shares<C: CvlrProp>(accounts: &[AccountInfo]) {
-    let iter = &mut accounts.iter();
-    let vault_info = next_account_info(iter).unwrap();
-    let vault_assets_account = next_account_info(iter).unwrap();
-    let assets_mint = next_account_info(iter).unwrap();
-    let shares_mint = next_account_info(iter).unwrap();
-    let user_assets_account = next_account_info(iter).unwrap();
-    let authority = next_account_info(iter).unwrap();
-    let user_shares_account = next_account_info(iter).unwrap();
+    let RedeemSharesAccounts {
+        vault_info,
+        vault_assets_account,
+        assets_mint,
+        shares_mint,
+        user_assets_account,
+        authority,
+        user_shares_account,
+        ..
+    } = RedeemSharesAccounts::unpack(accounts).unwrap();
 
     let pre = C::new(
         vault_info,
@@ -83,7 +93,7 @@ pub fn base_process_redeem_shares<C: CvlrProp>(accounts: &[AccountInfo]) {
     pre.assume_pre();
 
     let amount = nondet();
-    process_redeem_shares(accounts, amount).unwrap();
+    process_redeem_shares(&CvlrTokenProgram, accounts, amount, Slippage::default()).unwrap();
 
     let post = C::new(
         vault_info,
@@ -101,9 +111,11 @@ pub fn base_process_redeem_shares<C: CvlrProp>(accounts: &[AccountInfo]) {
 
 #[inline(always)]
 pub fn base_process_update_reward<C: CvlrProp>(accounts: &[AccountInfo]) {
-    let iter = &mut accounts.iter();
-    let vault_info = next_account_info(iter).unwrap();
-    let vault_assets_account = next_account_info(iter).unwrap();
+    let UpdateRewardAccounts {
+        vault_info,
+        vault_assets_account,
+        ..
+    } = UpdateRewardAccounts::unpack(accounts).unwrap();
 
     let pre = C::new(
         vault_info,
@@ -116,7 +128,7 @@ pub fn base_process_update_reward<C: CvlrProp>(accounts: &[AccountInfo]) {
     );
     pre.assume_pre();
 
-    process_update_reward(accounts).unwrap();
+    process_update_reward(&CvlrTokenProgram, accounts).unwrap();
 
     let post = C::new(
         vault_info,
@@ -134,12 +146,14 @@ pub fn base_process_update_reward<C: CvlrProp>(accounts: &[AccountInfo]) {
 
 #[inline(always)]
 pub fn base_process_slash<C: CvlrProp>(accounts: &[AccountInfo]) {
-    let iter = &mut accounts.iter();
-    let vault_info = next_account_info(iter).unwrap();
-    let vault_assets_account = next_account_info(iter).unwrap();
-    let user_assets_account = next_account_info(iter).unwrap();
-    let assets_mint = next_account_info(iter).unwrap();
-    let authority = next_account_info(iter).unwrap();
+    let SlashAccounts {
+        vault_info,
+        vault_assets_account,
+        user_token_account: user_assets_account,
+        assets_mint,
+        authority,
+        ..
+    } = SlashAccounts::unpack(accounts).unwrap();
 
     let pre = C::new(
         vault_info,
@@ -153,7 +167,7 @@ pub fn base_process_slash<C: CvlrProp>(accounts: &[AccountInfo]) {
     pre.assume_pre();
 
     let amount = nondet();
-    process_slash(accounts, amount).unwrap();
+    process_slash(&CvlrTokenProgram, accounts, amount).unwrap();
 
     let post = C::new(
         vault_info,
@@ -168,3 +182,41 @@ pub fn base_process_slash<C: CvlrProp>(accounts: &[AccountInfo]) {
     clog!(pre, post);
     post.check_post(&pre);
 }
+
+#[inline(always)]
+pub fn base_process_collect_fee<C: CvlrProp>(accounts: &[AccountInfo]) {
+    let CollectFeeAccounts {
+        vault_info,
+        vault_assets_account,
+        assets_mint,
+        fee_collect_account,
+        authority,
+        ..
+    } = CollectFeeAccounts::unpack(accounts).unwrap();
+
+    let pre = C::new(
+        vault_info,
+        vault_assets_account,
+        Some(assets_mint),
+        None,
+        Some(fee_collect_account),
+        Some(authority),
+        None,
+    );
+    pre.assume_pre();
+
+    process_collect_fee(&CvlrTokenProgram, accounts).unwrap();
+
+    let post = C::new(
+        vault_info,
+        vault_assets_account,
+        Some(assets_mint),
+        None,
+        Some(fee_collect_account),
+        Some(authority),
+        None,
+    );
+
+    clog!(pre, post);
+    post.check_post(&pre);
+}
@@ -1,5 +1,14 @@
-use crate::certora::specs::base::{base_deposit_assets, base_deposit_assets_with_fee};
-use crate::certora::specs::fees::props::FeeAssessedProp;
+use crate::certora::specs::base::{
+    base_accrue_management_fee, base_collect_fee, base_deposit_assets, base_deposit_assets_exact,
+    base_deposit_assets_with_fee, base_deposit_assets_with_fee_exact, base_process_slash,
+    base_redeem_shares, base_update_reward, base_withdraw_assets,
+};
+use crate::certora::specs::fees::performance_fee_props::{HighWaterMarkProp, PerformanceFeeProp};
+use crate::certora::specs::fees::props::{
+    FeeAccruedProp, FeeAssessedProp, FeeCollectedProp, ManagementFeeProp,
+};
+use crate::operations::vault_apply_param_change;
+use crate::state::{ParamChange, Vault, FEE_BPS_CAP};
 use cvlr::prelude::*;
 
 #[rule]
@@ -11,3 +20,72 @@ pub fn rule_fees_assessed_deposit_assets_with_fee() {
 pub fn rule_fees_assessed_deposit_assets() {
     base_deposit_assets::<FeeAssessedProp>();
 }
+
+#[rule]
+pub fn rule_fees_accrued_deposit_assets_with_fee() {
+    base_deposit_assets_with_fee::<FeeAccruedProp>();
+}
+
+#[rule]
+pub fn rule_fees_assessed_deposit_assets_with_fee_exact() {
+    base_deposit_assets_with_fee_exact::<FeeAssessedProp>();
+}
+
+#[rule]
+pub fn rule_fees_accrued_deposit_assets_exact() {
+    base_deposit_assets_exact::<FeeAccruedProp>();
+}
+
+#[rule]
+pub fn rule_fees_accrued_deposit_assets_with_fee_exact() {
+    base_deposit_assets_with_fee_exact::<FeeAccruedProp>();
+}
+
+#[rule]
+pub fn rule_fees_collect_preserves_depositor_assets() {
+    base_collect_fee::<FeeCollectedProp>();
+}
+
+#[rule]
+pub fn rule_fees_management_fee_bounded() {
+    base_accrue_management_fee::<ManagementFeeProp>();
+}
+
+#[rule]
+pub fn rule_fees_performance_fee_update_reward() {
+    base_update_reward::<PerformanceFeeProp>();
+}
+
+#[rule]
+pub fn rule_fees_high_water_mark_update_reward() {
+    base_update_reward::<HighWaterMarkProp>();
+}
+
+#[rule]
+pub fn rule_fees_high_water_mark_slash() {
+    base_process_slash::<HighWaterMarkProp>();
+}
+
+#[rule]
+pub fn rule_fees_assessed_redeem_shares() {
+    base_redeem_shares::<FeeAssessedProp>();
+}
+
+#[rule]
+pub fn rule_fees_assessed_withdraw_assets() {
+    base_withdraw_assets::<FeeAssessedProp>();
+}
+
+#[rule]
+pub fn rule_fees_param_change_fee_cap() {
+    let mut vault: Vault = nondet();
+    let fee_bps_pre = u64::from(vault.fee_bps);
+
+    let new_fee_bps: u64 = nondet();
+    vault_apply_param_change(&mut vault, ParamChange::FeeBps(new_fee_bps)).unwrap();
+
+    // -- a parameter change never raises the fee above the cap
+    let fee_bps_post = u64::from(vault.fee_bps);
+    clog!(fee_bps_pre, fee_bps_post);
+    cvlr_assert!(fee_bps_post <= fee_bps_pre || fee_bps_post <= FEE_BPS_CAP);
+}
@@ -1,2 +1,3 @@
 pub mod fees;
+pub mod performance_fee_props;
 pub mod props;
@@ -0,0 +1,116 @@
+use crate::certora::specs::base::{CvlrProp, OperationParams};
+use crate::operations::VaultEffect;
+use crate::state::Vault;
+use cvlr::mathint::NativeInt;
+use cvlr::{cvlr_assert_eq, cvlr_assert_ge, cvlr_assert_le, cvlr_assume};
+
+pub struct PerformanceFeeProp {
+    shares_total: NativeInt,
+    virtual_shares: NativeInt,
+    virtual_assets: NativeInt,
+    gross_assets: NativeInt,
+    performance_fee_bps: NativeInt,
+    high_water_mark_assets: NativeInt,
+    high_water_mark_shares: NativeInt,
+}
+
+mod log {
+    use super::*;
+    use cvlr::log::cvlr_log_with;
+    use cvlr::log::CvlrLog;
+
+    impl CvlrLog for PerformanceFeeProp {
+        #[inline(always)]
+        fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+            logger.log_scope_start(tag);
+            cvlr_log_with("virtual_assets", &self.virtual_assets, logger);
+            cvlr_log_with("virtual_shares", &self.virtual_shares, logger);
+            cvlr_log_with("gross_assets", &self.gross_assets, logger);
+            cvlr_log_with(
+                "high_water_mark_assets",
+                &self.high_water_mark_assets,
+                logger,
+            );
+            cvlr_log_with(
+                "high_water_mark_shares",
+                &self.high_water_mark_shares,
+                logger,
+            );
+            logger.log_scope_end(tag);
+        }
+    }
+}
+
+impl PerformanceFeeProp {
+    /// The high-water mark never goes down
+    fn check_mark_not_lowered(&self, old: &Self) {
+        cvlr_assert_ge!(
+            self.high_water_mark_assets * old.high_water_mark_shares,
+            old.high_water_mark_assets * self.high_water_mark_shares
+        );
+    }
+}
+
+/// property for `vault_update_reward`: the performance fee is a fraction of
+/// the reward, and is only charged on a share price above the high-water mark
+impl CvlrProp for PerformanceFeeProp {
+    fn new(vault: &Vault) -> Self {
+        Self {
+            shares_total: vault.num_shares().into(),
+            virtual_shares: NativeInt::from(vault.num_shares())
+                + NativeInt::from(vault.virtual_shares_offset()),
+            virtual_assets: NativeInt::from(vault.num_assets()) + NativeInt::from(1u64),
+            gross_assets: vault.gross_assets().into(),
+            performance_fee_bps: u64::from(vault.performance_fee_bps).into(),
+            high_water_mark_assets: u64::from(vault.high_water_mark_assets).into(),
+            high_water_mark_shares: u64::from(vault.high_water_mark_shares).into(),
+        }
+    }
+
+    fn assume_pre(&self) {
+        cvlr_assume!(self.high_water_mark_shares > NativeInt::from(0u64));
+    }
+
+    fn check_post(&self, old: &Self, _params: OperationParams, effect: VaultEffect) {
+        self.check_mark_not_lowered(old);
+        cvlr_assert_eq!(self.shares_total, old.shares_total);
+
+        // -- fee <= reward * bps / 10_000
+        cvlr_assert_le!(
+            NativeInt::from(effect.assets_to_fee) * NativeInt::from(10_000u64),
+            (self.gross_assets - old.gross_assets) * old.performance_fee_bps
+        );
+
+        // -- after paying the fee, the share price is still at or above the old mark
+        if effect.assets_to_fee > 0 {
+            cvlr_assert_ge!(
+                self.virtual_assets * old.high_water_mark_shares,
+                old.high_water_mark_assets * self.virtual_shares
+            );
+        }
+    }
+}
+
+/// The high-water mark is never lowered, in particular not by a slash
+pub struct HighWaterMarkProp(PerformanceFeeProp);
+
+impl cvlr::log::CvlrLog for HighWaterMarkProp {
+    #[inline(always)]
+    fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+        self.0.log(tag, logger)
+    }
+}
+
+impl CvlrProp for HighWaterMarkProp {
+    fn new(vault: &Vault) -> Self {
+        Self(PerformanceFeeProp::new(vault))
+    }
+
+    fn assume_pre(&self) {
+        self.0.assume_pre();
+    }
+
+    fn check_post(&self, old: &Self, _params: OperationParams, _effect: VaultEffect) {
+        self.0.check_mark_not_lowered(&old.0);
+    }
+}
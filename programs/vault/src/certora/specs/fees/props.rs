@@ -1,33 +1,58 @@
 use crate::certora::specs::base::{CvlrProp, OperationParams};
 use crate::operations::VaultEffect;
 use crate::state::Vault;
+use crate::utils::math::SECONDS_PER_YEAR;
+use cvlr::log::CvlrLog;
 use cvlr::mathint::NativeInt;
-use cvlr::{cvlr_assert_gt, cvlr_assert_le};
+use cvlr::{cvlr_assert_eq, cvlr_assert_ge, cvlr_assert_gt, cvlr_assert_le};
 
 pub struct FeeAssessedProp {
     fee_bps: NativeInt,
+    max_exit_fee_bps: NativeInt,
 }
 
 mod log {
     use super::*;
-    use cvlr::log::CvlrLog;
     impl CvlrLog for FeeAssessedProp {
         #[inline(always)]
         fn log(&self, _tag: &str, _logger: &mut cvlr::log::CvlrLogger) {}
     }
 }
 
-/// property for `vault_deposit_assets_with_fee`
+/// property for `vault_deposit_assets_with_fee`, and for the exit fee of
+/// redeem and withdraw
 impl CvlrProp for FeeAssessedProp {
     fn new(vault: &Vault) -> Self {
+        let max_exit_fee_bps = vault
+            .exit_fee_tiers
+            .iter()
+            .map(|tier| u64::from(tier.fee_bps))
+            .max()
+            .unwrap_or(0);
         Self {
             fee_bps: u64::from(vault.fee_bps).into(),
+            max_exit_fee_bps: max_exit_fee_bps.into(),
         }
     }
 
     fn assume_pre(&self) {}
 
-    fn check_post(&self, _old: &Self, params: OperationParams, effect: VaultEffect) {
+    fn check_post(&self, old: &Self, params: OperationParams, effect: VaultEffect) {
+        if effect.shares_to_burn > 0 {
+            // -- exit: the fee is taken on top of the assets paid to the user
+            let fee = NativeInt::from(effect.assets_to_fee);
+            let total = NativeInt::from(effect.assets_to_user) + fee;
+            let max_exit_fee_bps = old.max_exit_fee_bps;
+            cvlr::clog!(fee, total, max_exit_fee_bps);
+            cvlr_assert_le!(fee, total);
+            // -- the fee is rounded up by at most one unit
+            cvlr_assert_le!(
+                fee * NativeInt::from(10_000u64),
+                total * max_exit_fee_bps + NativeInt::from(10_000u64)
+            );
+            return;
+        }
+
         let tokens_amount = params.amount;
         let fee_bps = self.fee_bps;
         cvlr::clog!(tokens_amount, fee_bps, effect);
@@ -37,3 +62,145 @@ impl CvlrProp for FeeAssessedProp {
         }
     }
 }
+
+/// Accounting of the fees held in the vault assets account
+pub struct FeeAccountingProp {
+    assets: NativeInt,
+    shares: NativeInt,
+    fee_amount: NativeInt,
+}
+
+mod accounting_log {
+    use super::*;
+    use cvlr::log::cvlr_log_with;
+    use cvlr::log::CvlrLog;
+
+    impl CvlrLog for FeeAccountingProp {
+        #[inline(always)]
+        fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+            logger.log_scope_start(tag);
+            cvlr_log_with("assets", &self.assets, logger);
+            cvlr_log_with("shares", &self.shares, logger);
+            cvlr_log_with("fee_amount", &self.fee_amount, logger);
+            logger.log_scope_end(tag);
+        }
+    }
+}
+
+impl FeeAccountingProp {
+    fn new(vault: &Vault) -> Self {
+        Self {
+            assets: vault.num_assets().into(),
+            shares: vault.num_shares().into(),
+            fee_amount: vault.fee_amount().into(),
+        }
+    }
+}
+
+/// property for deposits: the assessed fee accrues to the vault
+pub struct FeeAccruedProp(FeeAccountingProp);
+
+impl CvlrLog for FeeAccruedProp {
+    #[inline(always)]
+    fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+        self.0.log(tag, logger)
+    }
+}
+
+impl CvlrProp for FeeAccruedProp {
+    fn new(vault: &Vault) -> Self {
+        Self(FeeAccountingProp::new(vault))
+    }
+
+    fn assume_pre(&self) {}
+
+    fn check_post(&self, old: &Self, _params: OperationParams, effect: VaultEffect) {
+        cvlr_assert_eq!(
+            self.0.fee_amount,
+            old.0.fee_amount + NativeInt::from(effect.assets_to_fee)
+        );
+        cvlr_assert_eq!(
+            self.0.assets,
+            old.0.assets + NativeInt::from(effect.assets_to_vault)
+        );
+    }
+}
+
+/// property for `vault_collect_fee`: exactly the accrued fees are paid out,
+/// depositor assets and shares are left untouched
+pub struct FeeCollectedProp(FeeAccountingProp);
+
+impl CvlrLog for FeeCollectedProp {
+    #[inline(always)]
+    fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+        self.0.log(tag, logger)
+    }
+}
+
+impl CvlrProp for FeeCollectedProp {
+    fn new(vault: &Vault) -> Self {
+        Self(FeeAccountingProp::new(vault))
+    }
+
+    fn assume_pre(&self) {}
+
+    fn check_post(&self, old: &Self, _params: OperationParams, effect: VaultEffect) {
+        cvlr_assert_eq!(NativeInt::from(effect.assets_to_user), old.0.fee_amount);
+        cvlr_assert_eq!(self.0.fee_amount, NativeInt::from(0u64));
+        cvlr_assert_eq!(self.0.assets, old.0.assets);
+        cvlr_assert_eq!(self.0.shares, old.0.shares);
+    }
+}
+
+/// property for `vault_accrue_management_fee`: fees are only taken from
+/// depositor assets, and never faster than the configured annual rate
+pub struct ManagementFeeProp {
+    accounting: FeeAccountingProp,
+    management_fee_bps: NativeInt,
+    last_fee_accrual_ts: i64,
+}
+
+impl CvlrLog for ManagementFeeProp {
+    #[inline(always)]
+    fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
+        self.accounting.log(tag, logger)
+    }
+}
+
+impl CvlrProp for ManagementFeeProp {
+    fn new(vault: &Vault) -> Self {
+        Self {
+            accounting: FeeAccountingProp::new(vault),
+            management_fee_bps: u64::from(vault.management_fee_bps).into(),
+            last_fee_accrual_ts: vault.last_fee_accrual_ts(),
+        }
+    }
+
+    fn assume_pre(&self) {}
+
+    fn check_post(&self, old: &Self, _params: OperationParams, effect: VaultEffect) {
+        cvlr_assert_ge!(self.last_fee_accrual_ts, old.last_fee_accrual_ts);
+        let elapsed: NativeInt = self
+            .last_fee_accrual_ts
+            .abs_diff(old.last_fee_accrual_ts)
+            .into();
+
+        // -- the fee moves from depositor assets to accrued fees
+        cvlr_assert_eq!(self.accounting.shares, old.accounting.shares);
+        cvlr_assert_eq!(
+            self.accounting.assets + NativeInt::from(effect.assets_to_fee),
+            old.accounting.assets
+        );
+        cvlr_assert_eq!(
+            self.accounting.fee_amount,
+            old.accounting.fee_amount + NativeInt::from(effect.assets_to_fee)
+        );
+
+        // -- fee <= assets * bps * elapsed / (10_000 * one year)
+        let one_year = NativeInt::from(10_000u64) * NativeInt::from(SECONDS_PER_YEAR);
+        cvlr_assert_le!(
+            NativeInt::from(effect.assets_to_fee) * one_year,
+            old.accounting.assets * old.management_fee_bps * elapsed
+        );
+    }
+}
@@ -1,6 +1,13 @@
 use crate::{operations::*, state::Vault};
 use cvlr::{mathint::NativeInt, prelude::*};
 
+/// Losses are measured without the exit fee, which is charged by design
+fn assume_no_exit_fee(vault: &Vault) {
+    for tier in &vault.exit_fee_tiers {
+        cvlr_assume!(u64::from(tier.fee_bps) == 0);
+    }
+}
+
 #[rule]
 pub fn rule_inflation_attack() {
     let mut vault: Vault = nondet();
@@ -60,7 +67,7 @@ pub fn rule_inflation_attack() {
     );
 
     // -- Bob redeems shares
-    let effects = vault_redeem_shares(&mut vault, bob_shares).unwrap();
+    let effects = vault_redeem_shares(&mut vault, bob_shares, None).unwrap();
     bob_shares = bob_shares.checked_sub(effects.shares_to_burn).unwrap();
     bob_assets = bob_assets.checked_add(effects.assets_to_user).unwrap();
 
@@ -87,6 +94,7 @@ pub fn rule_inflation_max_loss() {
     let mut vault: Vault = nondet();
 
     cvlr_assume!(vault.num_shares() <= vault.num_assets());
+    assume_no_exit_fee(&vault);
     clog!();
     clog!("Initial vault", vault);
 
@@ -117,7 +125,7 @@ pub fn rule_inflation_max_loss() {
     );
 
     // -- Alice redeems shares
-    let effects = vault_redeem_shares(&mut vault, alice_shares).unwrap();
+    let effects = vault_redeem_shares(&mut vault, alice_shares, None).unwrap();
     alice_shares = alice_shares.checked_sub(effects.shares_to_burn).unwrap();
     alice_assets = alice_assets.checked_add(effects.assets_to_user).unwrap();
 
@@ -144,3 +152,57 @@ pub fn rule_inflation_max_loss() {
     // -- loss is limited to the current share price rounded up
     cvlr_assert_ge!(alice_assets_post + share_price_pre, alice_assets_pre);
 }
+
+/// With virtual shares and assets, front-running the first deposit with a
+/// donation is never profitable, and the victim loses at most what the
+/// attacker put in the vault divided by the virtual shares offset. Fees are
+/// zero so that only the donation and rounding count as losses.
+#[rule]
+pub fn rule_inflation_offset_bound() {
+    let mut vault: Vault = nondet();
+    assume_no_exit_fee(&vault);
+    cvlr_assume!(u64::from(vault.fee_bps) == 0);
+
+    // -- the vault is empty, Bob is the first depositor
+    cvlr_assume!(vault.num_shares() == 0);
+    cvlr_assume!(vault.num_assets() == 0);
+    let offset: NativeInt = vault.virtual_shares_offset().into();
+    clog!("Initial vault", vault, offset);
+
+    // -- Bob buys some shares and donates assets to inflate the share price
+    let bob_deposit: u64 = nondet();
+    let bob_shares = vault_deposit_assets(&mut vault, bob_deposit)
+        .unwrap()
+        .shares_to_user;
+    let bob_donate: u64 = nondet();
+    let new_vault_asset_amt = vault.num_assets().checked_add(bob_donate).unwrap();
+    vault_update_reward(&mut vault, new_vault_asset_amt).unwrap();
+    let bob_spent = NativeInt::from(bob_deposit) + NativeInt::from(bob_donate);
+    clog!("Inflation", vault, bob_shares, bob_spent);
+
+    // -- Alice buys some shares
+    let alice_deposit: u64 = nondet();
+    let alice_shares = vault_deposit_assets(&mut vault, alice_deposit)
+        .unwrap()
+        .shares_to_user;
+    clog!("Alice purchase", vault, alice_deposit, alice_shares);
+
+    // -- both redeem their shares, Bob first
+    let bob_redeemed = vault_redeem_shares(&mut vault, bob_shares, None)
+        .unwrap()
+        .assets_to_user;
+    let alice_redeemed = vault_redeem_shares(&mut vault, alice_shares, None)
+        .unwrap()
+        .assets_to_user;
+    clog!("Redeem", vault, bob_redeemed, alice_redeemed);
+
+    // -- Bob did not make profit
+    cvlr_assert_le!(NativeInt::from(bob_redeemed), bob_spent);
+
+    // -- Alice loss times the offset is bounded by Bob's spending,
+    // -- plus one offset for rounding down the redemption
+    cvlr_assert_le!(
+        NativeInt::from(alice_deposit) * offset,
+        NativeInt::from(alice_redeemed) * offset + offset + bob_spent
+    );
+}
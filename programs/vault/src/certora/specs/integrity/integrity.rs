@@ -1,3 +1,5 @@
+use crate::certora::mocks::processor::CvlrTokenProgram;
+use crate::operations::Slippage;
 use crate::processor::{process_deposit, process_redeem_shares, process_slash};
 use crate::state::Vault;
 use cvlr::mathint::NativeInt;
@@ -34,7 +36,7 @@ pub fn rule_deposit_must_increase_assets() {
     let total_assets_pre = get_vault_total_assets!(vault_info);
     let amount = nondet();
 
-    process_deposit(&accounts, amount).unwrap();
+    process_deposit(&CvlrTokenProgram, &accounts, amount, Slippage::default()).unwrap();
 
     let total_assets_post = get_vault_total_assets!(vault_info);
     clog!(amount, total_assets_pre, total_assets_post);
@@ -55,7 +57,7 @@ pub fn rule_deposit_assets_and_shares_monotonicity() {
     let total_shares_pre = get_vault_total_shares!(vault_info);
     let amount = nondet();
 
-    process_deposit(&accounts, amount).unwrap();
+    process_deposit(&CvlrTokenProgram, &accounts, amount, Slippage::default()).unwrap();
 
     let total_assets_post = get_vault_total_assets!(vault_info);
     let total_shares_post = get_vault_total_shares!(vault_info);
@@ -83,7 +85,7 @@ pub fn rule_redeem_assets_and_shares_monotonicity() {
     // vault must be solvent
     cvlr_assume!(total_shares_pre <= total_assets_pre);
 
-    process_redeem_shares(&accounts, amount).unwrap();
+    process_redeem_shares(&CvlrTokenProgram, &accounts, amount, Slippage::default()).unwrap();
 
     let total_assets_post = get_vault_total_assets!(vault_info);
     let total_shares_post = get_vault_total_shares!(vault_info);
@@ -110,7 +112,7 @@ pub fn rule_slash_no_dilution() {
     let total_shares_pre: NativeInt = get_vault_total_shares!(vault_info).into();
 
     let amount = nondet();
-    process_slash(&accounts, amount).unwrap();
+    process_slash(&CvlrTokenProgram, &accounts, amount).unwrap();
 
     let total_assets_post: NativeInt = get_vault_total_assets!(vault_info).into();
     let total_shares_post: NativeInt = get_vault_total_shares!(vault_info).into();
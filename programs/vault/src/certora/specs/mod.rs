@@ -7,5 +7,6 @@ pub mod fees;
 pub mod inflation_attack;
 pub mod integrity;
 pub mod no_dilution;
+pub mod pause;
 pub mod solvency;
 pub mod vault_consistency;
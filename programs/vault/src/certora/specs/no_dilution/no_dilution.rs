@@ -1,6 +1,7 @@
 use crate::certora::specs::base::{
-    base_deposit_assets, base_deposit_assets_with_fee, base_process_slash, base_redeem_shares,
-    base_update_reward,
+    base_deposit_assets, base_deposit_assets_exact, base_deposit_assets_with_fee,
+    base_deposit_assets_with_fee_exact, base_mint_shares_exact, base_process_slash,
+    base_redeem_shares, base_update_reward, base_withdraw_assets,
 };
 use crate::certora::specs::no_dilution::props::NoDilutionProp;
 use cvlr::prelude::*;
@@ -15,11 +16,31 @@ pub fn rule_no_dilution_deposit_assets_with_fee() {
     base_deposit_assets_with_fee::<NoDilutionProp>();
 }
 
+#[rule]
+pub fn rule_no_dilution_deposit_assets_exact() {
+    base_deposit_assets_exact::<NoDilutionProp>();
+}
+
+#[rule]
+pub fn rule_no_dilution_deposit_assets_with_fee_exact() {
+    base_deposit_assets_with_fee_exact::<NoDilutionProp>();
+}
+
 #[rule]
 pub fn rule_no_dilution_redeem_shares() {
     base_redeem_shares::<NoDilutionProp>();
 }
 
+#[rule]
+pub fn rule_no_dilution_mint_shares_exact() {
+    base_mint_shares_exact::<NoDilutionProp>();
+}
+
+#[rule]
+pub fn rule_no_dilution_withdraw_assets() {
+    base_withdraw_assets::<NoDilutionProp>();
+}
+
 #[rule]
 pub fn rule_no_dilution_update_reward() {
     base_update_reward::<NoDilutionProp>();
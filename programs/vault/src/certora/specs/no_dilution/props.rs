@@ -26,11 +26,13 @@ mod log {
 }
 
 /// "no dilution" is a desired property for some operations: the ratio token_total / shares_total cannot decrease.
+/// Totals include the virtual shares and assets used by conversions.
 impl CvlrProp for NoDilutionProp {
     fn new(vault: &Vault) -> Self {
         Self {
-            shares_total: vault.num_shares().into(),
-            token_total: vault.num_assets().into(),
+            shares_total: NativeInt::from(vault.num_shares())
+                + NativeInt::from(vault.virtual_shares_offset()),
+            token_total: NativeInt::from(vault.num_assets()) + NativeInt::from(1u64),
         }
     }
 
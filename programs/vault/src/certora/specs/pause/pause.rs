@@ -0,0 +1,83 @@
+use crate::certora::nondet::nondet_user_holding;
+use crate::operations::*;
+use crate::state::{Vault, VaultAction};
+use cvlr::prelude::*;
+
+/// Generates a rule checking that an operation blocked by the vault status
+/// leaves `shares` and `assets` unchanged, whether it fails or not
+macro_rules! paused_rule {
+    ($rule:ident, $action:expr, |$vault:ident| $op:expr) => {
+        #[rule]
+        pub fn $rule() {
+            let mut $vault: Vault = nondet();
+            cvlr_assume!(!$vault.status().unwrap().allows($action));
+
+            let shares_pre = $vault.num_shares();
+            let assets_pre = $vault.num_assets();
+            let _ = $op;
+            let shares_post = $vault.num_shares();
+            let assets_post = $vault.num_assets();
+
+            clog!(shares_pre, assets_pre, shares_post, assets_post);
+            cvlr_assert_eq!(shares_post, shares_pre);
+            cvlr_assert_eq!(assets_post, assets_pre);
+        }
+    };
+}
+
+paused_rule!(rule_paused_deposit_assets, VaultAction::Deposit, |vault| {
+    vault_deposit_assets(&mut vault, nondet())
+});
+
+paused_rule!(
+    rule_paused_deposit_assets_with_fee,
+    VaultAction::Deposit,
+    |vault| vault_deposit_assets_with_fee(&mut vault, nondet())
+);
+
+paused_rule!(
+    rule_paused_deposit_assets_exact,
+    VaultAction::Deposit,
+    |vault| vault_deposit_assets_exact(&mut vault, nondet())
+);
+
+paused_rule!(
+    rule_paused_deposit_assets_with_fee_exact,
+    VaultAction::Deposit,
+    |vault| vault_deposit_assets_with_fee_exact(&mut vault, nondet())
+);
+
+paused_rule!(
+    rule_paused_mint_shares_exact,
+    VaultAction::Deposit,
+    |vault| { vault_mint_shares_exact(&mut vault, nondet()) }
+);
+
+paused_rule!(rule_paused_redeem_shares, VaultAction::Redeem, |vault| {
+    vault_redeem_shares(&mut vault, nondet(), nondet_user_holding())
+});
+
+paused_rule!(rule_paused_withdraw_assets, VaultAction::Redeem, |vault| {
+    vault_withdraw_assets(&mut vault, nondet(), nondet_user_holding())
+});
+
+paused_rule!(
+    rule_paused_update_reward,
+    VaultAction::UpdateReward,
+    |vault| { vault_update_reward(&mut vault, nondet()) }
+);
+
+paused_rule!(rule_paused_process_slash, VaultAction::Slash, |vault| {
+    vault_process_slash(&mut vault, nondet())
+});
+
+paused_rule!(rule_paused_collect_fee, VaultAction::CollectFee, |vault| {
+    vault_collect_fee(&mut vault)
+});
+
+// -- only a fully paused vault blocks redeem, and it accrues no management fee
+paused_rule!(
+    rule_paused_accrue_management_fee,
+    VaultAction::Redeem,
+    |vault| vault_accrue_management_fee(&mut vault, nondet())
+);
@@ -8,6 +8,7 @@ use std::mem::size_of;
 pub struct SolvencyInvariant {
     shares_total: NativeInt,
     token_total: NativeInt,
+    virtual_shares_offset: NativeInt,
 }
 
 mod log {
@@ -21,12 +22,14 @@ mod log {
             logger.log_scope_start(tag);
             cvlr_log_with("token_total", &self.token_total, logger);
             cvlr_log_with("shares_total", &self.shares_total, logger);
+            cvlr_log_with("virtual_shares_offset", &self.virtual_shares_offset, logger);
             logger.log_scope_end(tag);
         }
     }
 }
 
-/// Solvency is an invariant: the vault can never have more shares than tokens.
+/// Solvency is an invariant: the vault can never have more shares than tokens,
+/// scaled by the virtual shares offset.
 impl CvlrProp for SolvencyInvariant {
     fn new(
         vault_info_account: &AccountInfo,
@@ -43,14 +46,18 @@ impl CvlrProp for SolvencyInvariant {
         Self {
             shares_total: vault.num_shares().into(),
             token_total: vault.num_assets().into(),
+            virtual_shares_offset: vault.virtual_shares_offset().into(),
         }
     }
 
     fn assume_pre(&self) {
-        cvlr_assume!(self.shares_total <= self.token_total);
+        cvlr_assume!(self.shares_total <= self.token_total * self.virtual_shares_offset);
     }
 
     fn check_post(&self, _old: &Self) {
-        cvlr_assert_le!(self.shares_total, self.token_total);
+        cvlr_assert_le!(
+            self.shares_total,
+            self.token_total * self.virtual_shares_offset
+        );
     }
 }
@@ -1,6 +1,7 @@
 use crate::certora::specs::base::{
-    base_deposit_assets, base_deposit_assets_with_fee, base_process_slash, base_redeem_shares,
-    base_update_reward,
+    base_accrue_management_fee, base_deposit_assets, base_deposit_assets_exact,
+    base_deposit_assets_with_fee, base_deposit_assets_with_fee_exact, base_mint_shares_exact,
+    base_process_slash, base_redeem_shares, base_update_reward, base_withdraw_assets,
 };
 use crate::certora::specs::solvency::props::SolvencyInvariant;
 use cvlr::prelude::*;
@@ -15,11 +16,31 @@ pub fn rule_solvency_deposit_assets_with_fee() {
     base_deposit_assets_with_fee::<SolvencyInvariant>();
 }
 
+#[rule]
+pub fn rule_solvency_deposit_assets_exact() {
+    base_deposit_assets_exact::<SolvencyInvariant>();
+}
+
+#[rule]
+pub fn rule_solvency_deposit_assets_with_fee_exact() {
+    base_deposit_assets_with_fee_exact::<SolvencyInvariant>();
+}
+
 #[rule]
 pub fn rule_solvency_redeem_shares() {
     base_redeem_shares::<SolvencyInvariant>();
 }
 
+#[rule]
+pub fn rule_solvency_mint_shares_exact() {
+    base_mint_shares_exact::<SolvencyInvariant>();
+}
+
+#[rule]
+pub fn rule_solvency_withdraw_assets() {
+    base_withdraw_assets::<SolvencyInvariant>();
+}
+
 #[rule]
 pub fn rule_solvency_update_reward() {
     base_update_reward::<SolvencyInvariant>();
@@ -29,3 +50,8 @@ pub fn rule_solvency_update_reward() {
 pub fn rule_solvency_slash() {
     base_process_slash::<SolvencyInvariant>();
 }
+
+#[rule]
+pub fn rule_solvency_accrue_management_fee() {
+    base_accrue_management_fee::<SolvencyInvariant>();
+}
@@ -43,5 +43,7 @@ pub fn mul_div_ceil_equiv_err() {
 
     let native_res = crate::utils::math::math_native::mul_div_ceil(a, b, c);
     let certora_res = crate::utils::math::math_certora::mul_div_ceil(a, b, c);
+    // -- in logic, division by 0 is an arbitrary output, not an error
+    cvlr_assume!(c > 0);
     cvlr_assert_eq!(native_res.is_err(), certora_res.is_err());
 }
@@ -0,0 +1,88 @@
+use crate::certora::mocks::processor::CvlrTokenProgram;
+use crate::loaders::{
+    CollectFeeAccounts, DepositAccounts, RedeemSharesAccounts, SlashAccounts, UpdateRewardAccounts,
+};
+use crate::operations::Slippage;
+use crate::processor::{
+    process_collect_fee, process_deposit, process_deposit_exact, process_deposit_with_fee,
+    process_deposit_with_fee_exact, process_mint_shares_exact, process_redeem_shares,
+    process_slash, process_update_reward, process_withdraw_assets,
+};
+use cvlr::prelude::*;
+use cvlr_solana::cvlr_deserialize_nondet_accounts;
+
+/// Rule that `$process` never succeeds when any two of its accounts alias
+///
+/// Any aliasing could break `VaultConsistencyInvariant`, e.g. a deposit
+/// from the vault assets account into itself books assets the vault never
+/// received. `$accounts` gives the number of accounts of the instruction.
+macro_rules! aliasing_rule {
+    ($name:ident, $accounts:ident, |$accs:ident| $process:expr) => {
+        #[rule]
+        pub fn $name() {
+            let $accs = cvlr_deserialize_nondet_accounts();
+            let (a, b): (usize, usize) = (nondet(), nondet());
+            cvlr_assume!(a < b && b < $accounts::LEN && b < $accs.len());
+            cvlr_assume!($accs[a].key == $accs[b].key);
+
+            $process.unwrap();
+            cvlr_assert!(false);
+        }
+    };
+}
+
+aliasing_rule!(rule_aliasing_process_deposit, DepositAccounts, |accs| {
+    process_deposit(&CvlrTokenProgram, &accs, nondet(), Slippage::default())
+});
+
+aliasing_rule!(
+    rule_aliasing_process_deposit_exact,
+    DepositAccounts,
+    |accs| { process_deposit_exact(&CvlrTokenProgram, &accs, nondet(), Slippage::default()) }
+);
+
+aliasing_rule!(
+    rule_aliasing_process_deposit_with_fee,
+    DepositAccounts,
+    |accs| process_deposit_with_fee(&CvlrTokenProgram, &accs, nondet(), Slippage::default())
+);
+
+aliasing_rule!(
+    rule_aliasing_process_deposit_with_fee_exact,
+    DepositAccounts,
+    |accs| process_deposit_with_fee_exact(&CvlrTokenProgram, &accs, nondet(), Slippage::default())
+);
+
+aliasing_rule!(
+    rule_aliasing_process_mint_shares_exact,
+    DepositAccounts,
+    |accs| process_mint_shares_exact(&CvlrTokenProgram, &accs, nondet(), Slippage::default())
+);
+
+aliasing_rule!(
+    rule_aliasing_process_redeem_shares,
+    RedeemSharesAccounts,
+    |accs| process_redeem_shares(&CvlrTokenProgram, &accs, nondet(), Slippage::default())
+);
+
+aliasing_rule!(
+    rule_aliasing_process_withdraw_assets,
+    RedeemSharesAccounts,
+    |accs| process_withdraw_assets(&CvlrTokenProgram, &accs, nondet(), Slippage::default())
+);
+
+aliasing_rule!(
+    rule_aliasing_process_update_reward,
+    UpdateRewardAccounts,
+    |accs| process_update_reward(&CvlrTokenProgram, &accs)
+);
+
+aliasing_rule!(rule_aliasing_process_slash, SlashAccounts, |accs| {
+    process_slash(&CvlrTokenProgram, &accs, nondet())
+});
+
+aliasing_rule!(
+    rule_aliasing_process_collect_fee,
+    CollectFeeAccounts,
+    |accs| { process_collect_fee(&CvlrTokenProgram, &accs) }
+);
@@ -1,2 +1,3 @@
+pub mod aliasing;
 pub mod props;
 pub mod vault_consistency;
@@ -6,7 +6,7 @@ use solana_program::account_info::AccountInfo;
 use std::mem::size_of;
 
 pub struct VaultConsistencyInvariant {
-    vault_assets: NativeInt,
+    vault_gross_assets: NativeInt,
     vault_shares: NativeInt,
     account_tokens: NativeInt,
     mint_shares: Option<NativeInt>,
@@ -21,7 +21,7 @@ mod log {
         #[inline(always)]
         fn log(&self, tag: &str, logger: &mut cvlr::log::CvlrLogger) {
             logger.log_scope_start(tag);
-            cvlr_log_with("vault_assets", &self.vault_assets, logger);
+            cvlr_log_with("vault_gross_assets", &self.vault_gross_assets, logger);
             cvlr_log_with("vault_shares", &self.vault_shares, logger);
             cvlr_log_with("account_tokens", &self.account_tokens, logger);
             cvlr_log_with("mint_shares", &self.mint_shares, logger);
@@ -31,6 +31,7 @@ mod log {
 }
 
 /// Vault's recorded asset/shares amount is consistent with the actual token balances in its associated SPL Token accounts.
+/// Recorded assets include the fees accrued in the vault assets account.
 impl CvlrProp for VaultConsistencyInvariant {
     fn new(
         vault_info_account: &AccountInfo,
@@ -45,7 +46,7 @@ impl CvlrProp for VaultConsistencyInvariant {
         let vault = bytemuck::from_bytes::<Vault>(&data[0..size_of::<Vault>()]);
 
         Self {
-            vault_assets: vault.num_assets().into(),
+            vault_gross_assets: vault.gross_assets().into(),
             vault_shares: vault.num_shares().into(),
             account_tokens: cvlr_solana::token::spl_token_account_get_amount(vault_assets_account)
                 .into(),
@@ -57,14 +58,14 @@ impl CvlrProp for VaultConsistencyInvariant {
     }
 
     fn assume_pre(&self) {
-        cvlr_assume!(self.vault_assets <= self.account_tokens);
+        cvlr_assume!(self.vault_gross_assets <= self.account_tokens);
         if let Some(mint_shares) = self.mint_shares {
             cvlr_assume!(self.vault_shares == mint_shares);
         }
     }
 
     fn check_post(&self, _old: &Self) {
-        cvlr_assert_le!(self.vault_assets, self.account_tokens);
+        cvlr_assert_le!(self.vault_gross_assets, self.account_tokens);
         if let Some(mint_shares) = self.mint_shares {
             cvlr_assert_eq!(self.vault_shares, mint_shares);
         }
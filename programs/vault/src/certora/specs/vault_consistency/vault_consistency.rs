@@ -1,5 +1,5 @@
 use crate::certora::specs::base_processor::{
-    base_process_deposit, base_process_redeem_shares, base_process_slash,
+    base_process_collect_fee, base_process_deposit, base_process_redeem_shares, base_process_slash,
     base_process_update_reward,
 };
 use crate::certora::specs::vault_consistency::props::VaultConsistencyInvariant;
@@ -29,3 +29,9 @@ pub fn rule_vault_consistency_process_slash() {
     let accs = cvlr_deserialize_nondet_accounts();
     base_process_slash::<VaultConsistencyInvariant>(&accs);
 }
+
+#[rule]
+pub fn rule_vault_consistency_process_collect_fee() {
+    let accs = cvlr_deserialize_nondet_accounts();
+    base_process_collect_fee::<VaultConsistencyInvariant>(&accs);
+}
@@ -0,0 +1,5 @@
+use solana_program::entrypoint;
+
+use crate::processor::process_instruction;
+
+entrypoint!(process_instruction);
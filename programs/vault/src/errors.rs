@@ -1,11 +1,26 @@
 use solana_program::program_error::ProgramError;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VaultError {
     Unspecified,
     MathOverflow,
     GuardFail,
     SelfTransfer,
+    AlreadyInitialized,
+    InvalidAccountOwner,
+    InvalidDiscriminator,
+    UninitializedAccount,
+    UnsupportedVersion,
+    MigrationRequired,
+    InvalidLayout,
+    FeeCapExceeded,
+    TimelockNotExpired,
+    MissingRole,
+    RoleTableFull,
+    VaultPaused,
+    SlippageExceeded,
+    DeadlineExceeded,
+    DuplicateAccount,
 }
 
 // Define a custom Result type
@@ -1,4 +1,16 @@
+use bytemuck::{Pod, Zeroable};
 use num_enum::TryFromPrimitive;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use spl_pod::primitives::{PodI64, PodU64};
+
+use crate::{
+    operations::Slippage,
+    state::{AuthorityRole, ExitFeeTier, ParamChange, VaultStatus, EXIT_FEE_TIERS},
+    utils::guards::require_eq,
+};
+
+/// Version of the instruction data layout understood by the program
+pub const INSTRUCTION_DATA_VERSION: u8 = 1;
 
 #[repr(u8)]
 #[derive(TryFromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
@@ -6,4 +18,293 @@ pub enum CertoraVaultInstruction {
     CreateVault = 0,
     Deposit = 1,
     DepositWithFee = 2,
+    DepositExact = 3,
+    DepositWithFeeExact = 4,
+    RedeemShares = 5,
+    UpdateReward = 6,
+    Slash = 7,
+    CollectFee = 8,
+    MigrateVault = 9,
+    MintSharesExact = 10,
+    WithdrawAssets = 11,
+    Preview = 12,
+    OpenPosition = 13,
+    QueueParamChange = 14,
+    ExecuteParamChange = 15,
+    CancelParamChange = 16,
+    ProposeAuthority = 17,
+    AcceptAuthority = 18,
+    CancelAuthority = 19,
+    SetRoles = 20,
+    SetStatus = 21,
+}
+
+/// Quote requested by [`CertoraVaultInstruction::Preview`], passed as the
+/// first byte of `args`
+#[repr(u8)]
+#[derive(TryFromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PreviewKind {
+    Deposit = 0,
+    Mint = 1,
+    Withdraw = 2,
+    Redeem = 3,
+    MaxDeposit = 4,
+    MaxRedeem = 5,
+}
+
+impl PreviewKind {
+    pub fn unpack(args: &[u8]) -> Result<Self, ProgramError> {
+        args.first()
+            .and_then(|&kind| Self::try_from(kind).ok())
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+}
+
+impl AuthorityRole {
+    /// Role of [`CertoraVaultInstruction::AcceptAuthority`] and
+    /// [`CertoraVaultInstruction::CancelAuthority`], passed as the first byte of `args`
+    pub fn unpack(args: &[u8]) -> Result<Self, ProgramError> {
+        args.first()
+            .and_then(|&role| Self::try_from(role).ok())
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+}
+
+impl VaultStatus {
+    /// Status of [`CertoraVaultInstruction::SetStatus`], passed as the first byte of `args`
+    pub fn unpack(args: &[u8]) -> Result<Self, ProgramError> {
+        args.first()
+            .and_then(|&status| Self::try_from(status).ok())
+            .ok_or(ProgramError::InvalidInstructionData)
+    }
+}
+
+/// Decoded instruction data
+///
+/// Layout: `tag: u8 | version: u8 | amount: u64 (LE) | args: [u8]`
+///
+/// `amount` is interpreted by each instruction (assets or shares) and is
+/// ignored by instructions that do not need it. `args` carries optional
+/// instruction specific arguments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VaultInstructionData<'a> {
+    pub instruction: CertoraVaultInstruction,
+    pub amount: u64,
+    pub args: &'a [u8],
+}
+
+impl<'a> VaultInstructionData<'a> {
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let instruction = CertoraVaultInstruction::try_from(tag)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+        let (&version, rest) = rest
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        require_eq!(
+            version,
+            INSTRUCTION_DATA_VERSION,
+            ProgramError::InvalidInstructionData
+        );
+
+        let amount = rest
+            .get(..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(Self {
+            instruction,
+            amount,
+            args: &rest[8..],
+        })
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(10 + self.args.len());
+        data.push(self.instruction as u8);
+        data.push(INSTRUCTION_DATA_VERSION);
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(self.args);
+        data
+    }
+}
+
+/// Arguments of [`CertoraVaultInstruction::CreateVault`]
+#[repr(C)]
+#[derive(Pod, Zeroable, Debug, Copy, Clone, PartialEq)]
+pub struct CreateVaultArgs {
+    pub admin: Pubkey,
+    pub slash_admin: Pubkey,
+    pub fee_token_account: Pubkey,
+    pub fee_bps: PodU64,
+    pub decimals_offset: u8,
+    pub management_fee_bps: PodU64,
+    pub performance_fee_bps: PodU64,
+    pub exit_fee_tiers: [ExitFeeTier; EXIT_FEE_TIERS],
+    pub exit_fee_holding_period: PodI64,
+    pub exit_fee_held_bps: PodU64,
+}
+
+impl CreateVaultArgs {
+    pub fn unpack(args: &[u8]) -> Result<&Self, ProgramError> {
+        bytemuck::try_from_bytes(args).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// Arguments of [`CertoraVaultInstruction::QueueParamChange`]
+///
+/// `value` holds a pubkey, or a little endian `u64` for fee rates
+#[repr(C)]
+#[derive(Pod, Zeroable, Debug, Copy, Clone, PartialEq)]
+pub struct QueueParamChangeArgs {
+    pub kind: u8,
+    pub value: [u8; 32],
+}
+
+impl QueueParamChangeArgs {
+    pub fn unpack(args: &[u8]) -> Result<&Self, ProgramError> {
+        bytemuck::try_from_bytes(args).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+
+    pub fn change(&self) -> Result<ParamChange, ProgramError> {
+        ParamChange::decode(self.kind, &self.value)
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// Arguments of [`CertoraVaultInstruction::ProposeAuthority`]
+#[repr(C)]
+#[derive(Pod, Zeroable, Debug, Copy, Clone, PartialEq)]
+pub struct ProposeAuthorityArgs {
+    pub role: u8,
+    pub new_authority: Pubkey,
+}
+
+impl ProposeAuthorityArgs {
+    pub fn unpack(args: &[u8]) -> Result<&Self, ProgramError> {
+        bytemuck::try_from_bytes(args).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+
+    pub fn role(&self) -> Result<AuthorityRole, ProgramError> {
+        AuthorityRole::try_from(self.role).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// Arguments of [`CertoraVaultInstruction::SetRoles`]
+#[repr(C)]
+#[derive(Pod, Zeroable, Debug, Copy, Clone, PartialEq)]
+pub struct SetRolesArgs {
+    pub authority: Pubkey,
+    /// Bitmask of [`crate::state::Role::mask`], zero revokes every role
+    pub roles: u8,
+}
+
+impl SetRolesArgs {
+    pub fn unpack(args: &[u8]) -> Result<&Self, ProgramError> {
+        bytemuck::try_from_bytes(args).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// Optional slippage bounds of deposits and withdrawals, see [`Slippage`]
+///
+/// Zero disables a bound, and empty `args` disable them all.
+#[repr(C)]
+#[derive(Pod, Zeroable, Debug, Copy, Clone, PartialEq)]
+pub struct SlippageArgs {
+    pub min_shares_out: PodU64,
+    pub min_assets_out: PodU64,
+    pub max_assets_in: PodU64,
+    pub max_shares_in: PodU64,
+    pub deadline_slot: PodU64,
+}
+
+impl SlippageArgs {
+    pub fn unpack(args: &[u8]) -> Result<Slippage, ProgramError> {
+        if args.is_empty() {
+            return Ok(Slippage::default());
+        }
+        let args: &Self =
+            bytemuck::try_from_bytes(args).map_err(|_| ProgramError::InvalidInstructionData)?;
+        let bound = |value: PodU64| Some(u64::from(value)).filter(|&value| value != 0);
+        Ok(Slippage {
+            min_shares_out: bound(args.min_shares_out),
+            min_assets_out: bound(args.min_assets_out),
+            max_assets_in: bound(args.max_assets_in),
+            max_shares_in: bound(args.max_shares_in),
+            deadline_slot: bound(args.deadline_slot),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_roundtrip() {
+        let ix = VaultInstructionData {
+            instruction: CertoraVaultInstruction::RedeemShares,
+            amount: 42,
+            args: &[1, 2, 3],
+        };
+        assert_eq!(VaultInstructionData::unpack(&ix.pack()).unwrap(), ix);
+    }
+
+    #[test]
+    fn test_unpack_malformed() {
+        // -- empty data
+        assert!(VaultInstructionData::unpack(&[]).is_err());
+        // -- unknown tag
+        assert!(VaultInstructionData::unpack(&[0xff, INSTRUCTION_DATA_VERSION]).is_err());
+        // -- unsupported version
+        assert!(VaultInstructionData::unpack(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        // -- truncated amount
+        assert!(VaultInstructionData::unpack(&[1, INSTRUCTION_DATA_VERSION, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_preview_kind() {
+        assert_eq!(PreviewKind::unpack(&[5]).unwrap(), PreviewKind::MaxRedeem);
+        assert!(PreviewKind::unpack(&[]).is_err());
+        assert!(PreviewKind::unpack(&[6]).is_err());
+    }
+
+    #[test]
+    fn test_unpack_param_change() {
+        let (kind, value) = ParamChange::FeeBps(250).encode();
+        let mut args = vec![kind as u8];
+        args.extend_from_slice(&value);
+        let args = QueueParamChangeArgs::unpack(&args).unwrap();
+        assert_eq!(args.change().unwrap(), ParamChange::FeeBps(250));
+
+        // -- unknown parameter
+        let args = QueueParamChangeArgs {
+            kind: 4,
+            value: [0; 32],
+        };
+        assert!(args.change().is_err());
+    }
+
+    #[test]
+    fn test_unpack_slippage() {
+        assert_eq!(SlippageArgs::unpack(&[]).unwrap(), Slippage::default());
+
+        let args = SlippageArgs {
+            min_shares_out: 10.into(),
+            min_assets_out: 0.into(),
+            max_assets_in: 0.into(),
+            max_shares_in: 0.into(),
+            deadline_slot: 7.into(),
+        };
+        let slippage = SlippageArgs::unpack(bytemuck::bytes_of(&args)).unwrap();
+        assert_eq!(slippage.min_shares_out, Some(10));
+        assert_eq!(slippage.min_assets_out, None);
+        assert_eq!(slippage.deadline_slot, Some(7));
+
+        assert!(SlippageArgs::unpack(&[1, 2, 3]).is_err());
+    }
 }
@@ -1,8 +1,14 @@
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
 pub mod errors;
 pub mod instruction;
 pub mod loaders;
 pub mod operations;
 pub mod processor;
+#[cfg(all(feature = "scenario", not(target_os = "solana")))]
+pub mod scenario;
+#[cfg(not(target_os = "solana"))]
+pub mod simulator;
 pub mod state;
 pub mod utils;
 
@@ -0,0 +1,195 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    program_error::ProgramError,
+};
+
+use crate::{
+    state::{AuthorityRole, Role, VaultStatus},
+    utils::guards::{require, require_eq},
+};
+
+use super::{RequireRole, Signer, VaultInfo};
+
+/// Accounts of the instructions run by the current holder of a role:
+/// proposing a new key and cancelling a proposal
+pub struct AuthorityHolderContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    // current holder of the role
+    pub authority: Signer<'info>,
+}
+
+impl<'info> AuthorityHolderContext<'info> {
+    pub fn validate(self, role: AuthorityRole) -> Result<Self, ProgramError> {
+        let vault = self.vault_info.get()?;
+        require_eq!(
+            vault.authority(role),
+            self.authority.as_ref().key,
+            ProgramError::InvalidArgument
+        );
+        drop(vault);
+        Ok(self)
+    }
+
+    pub fn load(
+        accounts: &[AccountInfo<'info>],
+        role: AuthorityRole,
+    ) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        Self {
+            vault_info: next_account_info(iter)?.try_into()?,
+            authority: next_account_info(iter)?.try_into()?,
+        }
+        .validate(role)
+    }
+}
+
+/// Accounts of the instruction that completes a rotation
+pub struct PendingAuthorityContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    // key proposed for the role
+    pub authority: Signer<'info>,
+}
+
+impl<'info> PendingAuthorityContext<'info> {
+    pub fn validate(self, role: AuthorityRole) -> Result<Self, ProgramError> {
+        let vault = self.vault_info.get()?;
+        let pending = vault.pending_authority(role);
+        require!(
+            pending == Some(self.authority.as_ref().key),
+            ProgramError::InvalidArgument
+        );
+        drop(vault);
+        Ok(self)
+    }
+
+    pub fn load(
+        accounts: &[AccountInfo<'info>],
+        role: AuthorityRole,
+    ) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        Self {
+            vault_info: next_account_info(iter)?.try_into()?,
+            authority: next_account_info(iter)?.try_into()?,
+        }
+        .validate(role)
+    }
+}
+
+/// Accounts of the instruction that grants and revokes roles
+pub struct SetRolesContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    // vault admin
+    pub authority: RequireRole<'info>,
+}
+
+impl<'info> SetRolesContext<'info> {
+    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        let vault_info: VaultInfo = next_account_info(iter)?.try_into()?;
+        Ok(Self {
+            authority: RequireRole::load(next_account_info(iter)?, &vault_info, Role::Admin)?,
+            vault_info,
+        })
+    }
+}
+
+/// Accounts of the instruction that changes the vault status
+pub struct SetStatusContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    // pauser, or the vault admin to wind the vault down
+    pub authority: RequireRole<'info>,
+}
+
+impl<'info> SetStatusContext<'info> {
+    pub fn load(
+        accounts: &[AccountInfo<'info>],
+        status: VaultStatus,
+    ) -> Result<Self, ProgramError> {
+        let role = match status {
+            VaultStatus::WindDown => Role::Admin,
+            _ => Role::Pauser,
+        };
+        let iter = &mut accounts.iter();
+        let vault_info: VaultInfo = next_account_info(iter)?.try_into()?;
+        Ok(Self {
+            authority: RequireRole::load(next_account_info(iter)?, &vault_info, role)?,
+            vault_info,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operations::{vault_accept_authority, vault_propose_authority, vault_set_status},
+        state::{Vault, VAULT_DISCRIMINATOR, VAULT_VERSION},
+        VaultError,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    fn set_status(accounts: &[AccountInfo], status: VaultStatus) -> Result<(), ProgramError> {
+        let ctx = SetStatusContext::load(accounts, status)?;
+        let mut vault = ctx.vault_info.get_mut()?;
+        vault_set_status(&mut vault, status)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotated_admin_keeps_default_roles() {
+        let (old_admin, new_admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut vault = Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            admin: old_admin,
+            assets_mint: Pubkey::new_unique(),
+            shares_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        vault_propose_authority(&mut vault, AuthorityRole::Admin, new_admin).unwrap();
+        vault_accept_authority(&mut vault, AuthorityRole::Admin).unwrap();
+
+        let vault_pk = Pubkey::new_unique();
+        let mut data = bytemuck::bytes_of(&vault).to_vec();
+        let (mut lamports, mut new_lamports, mut old_lamports) = (0, 0, 0);
+        let (mut new_data, mut old_data) = ([0u8; 0], [0u8; 0]);
+        let vault_info = AccountInfo::new(
+            &vault_pk,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let new_info = AccountInfo::new(
+            &new_admin,
+            true,
+            false,
+            &mut new_lamports,
+            &mut new_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let old_info = AccountInfo::new(
+            &old_admin,
+            true,
+            false,
+            &mut old_lamports,
+            &mut old_data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        let new_admin_accounts = [vault_info.clone(), new_info];
+        set_status(&new_admin_accounts, VaultStatus::DepositsPaused).unwrap();
+        set_status(&new_admin_accounts, VaultStatus::Active).unwrap();
+        assert_eq!(
+            set_status(&[vault_info, old_info], VaultStatus::FullyPaused),
+            Err(VaultError::MissingRole.into())
+        );
+    }
+}
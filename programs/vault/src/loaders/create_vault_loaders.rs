@@ -0,0 +1,94 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    shares_mint_authority_seeds,
+    utils::guards::{require, require_eq},
+    vault_assets_account_seeds, vault_assets_account_seeds_with_bump,
+};
+
+use super::{check_distinct_accounts, MintInfo, Signer, SplTokenProgramInfo, SystemProgramInfo};
+
+pub struct CreateVaultContext<'info> {
+    // the new vault account, must sign its own creation
+    pub vault_info: Signer<'info>,
+    // PDA token account that holds the vault assets
+    pub vault_assets_account: AccountInfo<'info>,
+    // mint for assets token
+    pub assets_mint: MintInfo<'info>,
+    // the new shares mint, must sign its own creation
+    pub shares_mint: Signer<'info>,
+    // pays for the rent of all new accounts
+    pub payer: Signer<'info>,
+    pub spl_token_program: SplTokenProgramInfo<'info>,
+    pub system_program: SystemProgramInfo<'info>,
+
+    pub vault_assets_account_bump: u8,
+    pub shares_mint_authority: Pubkey,
+    pub shares_mint_authority_bump: u8,
+}
+
+impl<'info> CreateVaultContext<'info> {
+    pub fn validate(self) -> Result<Self, ProgramError> {
+        let vault_info = self.vault_info.as_ref();
+
+        // -- refuse to initialize an account that is already in use
+        require!(
+            vault_info.data_is_empty() && system_program::check_id(vault_info.owner),
+            crate::errors::VaultError::AlreadyInitialized.into()
+        );
+
+        let expected_pk = Pubkey::create_program_address(
+            vault_assets_account_seeds_with_bump!(vault_info.key, self.vault_assets_account_bump),
+            &crate::ID,
+        )?;
+        require_eq!(
+            self.vault_assets_account.key,
+            &expected_pk,
+            ProgramError::InvalidArgument
+        );
+
+        // -- the vault, its mints, its assets account and the payer are all distinct
+        check_distinct_accounts(&[
+            Some(vault_info),
+            Some(&self.vault_assets_account),
+            Some(self.assets_mint.as_ref()),
+            Some(self.shares_mint.as_ref()),
+            Some(self.payer.as_ref()),
+        ])?;
+
+        Ok(self)
+    }
+
+    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        let vault_info: Signer = next_account_info(iter)?.try_into()?;
+
+        let (_, vault_assets_account_bump) = Pubkey::find_program_address(
+            vault_assets_account_seeds!(vault_info.as_ref().key),
+            &crate::ID,
+        );
+        let (shares_mint_authority, shares_mint_authority_bump) = Pubkey::find_program_address(
+            shares_mint_authority_seeds!(vault_info.as_ref().key),
+            &crate::ID,
+        );
+
+        Self {
+            vault_info,
+            vault_assets_account: next_account_info(iter)?.clone(),
+            assets_mint: MintInfo::readonly(next_account_info(iter)?)?,
+            shares_mint: next_account_info(iter)?.try_into()?,
+            payer: next_account_info(iter)?.try_into()?,
+            spl_token_program: next_account_info(iter)?.try_into()?,
+            system_program: next_account_info(iter)?.try_into()?,
+            vault_assets_account_bump,
+            shares_mint_authority,
+            shares_mint_authority_bump,
+        }
+        .validate()
+    }
+}
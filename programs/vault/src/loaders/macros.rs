@@ -0,0 +1,224 @@
+/// Declares an instruction account context
+///
+/// ```text
+/// vault_accounts! {
+///     pub struct SlashContext, SlashAccounts {
+///         vault_info: VaultInfo<'info> = try_into [writable],
+///         vault_assets_account: VaultAssetsAccount<'info> = try_into if vault_pda,
+///         user_token_account: TokenAccountInfo<'info> = try_into if mint(assets_mint),
+///         assets_mint: MintInfo<'info> = readonly if has_one(assets_mint),
+///         authority: RequireRole<'info> = role(vault_info, Slasher),
+///         spl_token_program: SplTokenProgramInfo<'info> = try_into,
+///     }
+/// }
+/// ```
+///
+/// Accounts are read in declaration order. `SlashAccounts` holds them
+/// unwrapped, for the Certora harnesses, and `SlashContext` holds them
+/// wrapped by their loader:
+/// - `try_into`, `readonly`: the wrapper constructor of that name
+/// - `role(vault, Role)`: a [`super::RequireRole`] of the vault loaded as `vault`
+/// - `optional(f)`, `optional_try(f)`: trailing optional account wrapped by `f`
+///
+/// Flags in brackets, `writable` and `signer`, are checked on required
+/// accounts before they are wrapped. `validate` rejects any account passed
+/// twice, see [`super::check_distinct_accounts`], then checks the
+/// constraints after `if`, separated by `&`, against the `Vault` of
+/// `vault_info`:
+/// - `has_one(field)`: the account key is `vault.field`
+/// - `mint(field)`: the token account holds tokens of `vault.field`
+/// - `authority(account)`: `account` is the owner or delegate of the token account
+/// - `vault_pda`: the account is the PDA of the vault, see `check_vault`
+///
+/// An optional trailing `validate(ctx, vault_pk, vault) { .. }` block runs
+/// after the constraints, with the context, the vault key and the vault.
+macro_rules! vault_accounts {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident, $accounts:ident {
+            $(
+                $field:ident : $ty:ty = $loader:ident $(($($larg:tt)*))?
+                    $([$($flag:ident),*])?
+                    $(if $($check:ident $(($($carg:tt)*))?)&+)?
+            ),* $(,)?
+        }
+        $(validate($ctx:ident, $vault_pk:ident, $vault:ident) $body:block)?
+    ) => {
+        $crate::loaders::vault_accounts! {
+            @expand
+            [$(#[$meta])*] $vis $name, $accounts {
+                $(
+                    $field : $ty = [$loader $(($($larg)*))?]
+                        [$($($flag),*)?]
+                        [$($($check $(($($carg)*))?)&+)?]
+                ),*
+            }
+            $(validate($ctx, $vault_pk, $vault) $body)?
+        }
+    };
+
+    (
+        @expand
+        [$($meta:tt)*] $vis:vis $name:ident, $accounts:ident {
+            $($field:ident : $ty:ty = [$($loader:tt)*] [$($flag:ident),*] [$($check:tt)*]),*
+        }
+    ) => {
+        $crate::loaders::vault_accounts! {
+            @expand
+            [$($meta)*] $vis $name, $accounts {
+                $($field : $ty = [$($loader)*] [$($flag),*] [$($check)*]),*
+            }
+            validate(ctx, vault_pk, vault) {}
+        }
+    };
+
+    (
+        @expand
+        [$($meta:tt)*] $vis:vis $name:ident, $accounts:ident {
+            $($field:ident : $ty:ty = [$($loader:tt)*] [$($flag:ident),*] [$($check:tt)*]),*
+        }
+        validate($ctx:ident, $vault_pk:ident, $vault:ident) $body:block
+    ) => {
+        /// Accounts of
+        #[doc = concat!("[`", stringify!($name), "`]")]
+        /// in instruction order, not validated
+        $vis struct $accounts<'a, 'info> {
+            $(pub $field: $crate::loaders::vault_accounts!(@raw_ty 'a, 'info, $($loader)*),)*
+        }
+
+        impl<'a, 'info> $accounts<'a, 'info> {
+            /// Number of accounts, optional ones included
+            pub const LEN: usize = [$(stringify!($field)),*].len();
+
+            pub fn unpack(
+                accounts: &'a [::solana_program::account_info::AccountInfo<'info>],
+            ) -> Result<Self, ::solana_program::program_error::ProgramError> {
+                let iter = &mut accounts.iter();
+                Ok(Self {
+                    $($field: $crate::loaders::vault_accounts!(@raw iter, $($loader)*),)*
+                })
+            }
+        }
+
+        $($meta)*
+        $vis struct $name<'info> {
+            $(pub $field: $ty,)*
+        }
+
+        impl<'info> $name<'info> {
+            #[allow(unused_variables)]
+            pub fn validate(self) -> Result<Self, ::solana_program::program_error::ProgramError> {
+                {
+                    let $ctx = &self;
+                    $crate::loaders::check_distinct_accounts(&[
+                        $($crate::loaders::vault_accounts!(@info $ctx.$field, $($loader)*),)*
+                    ])?;
+
+                    let $vault_pk = self.vault_info.as_ref().key;
+                    let $vault = self.vault_info.get()?;
+                    $(
+                        $crate::loaders::vault_accounts!(
+                            @checks $ctx, $vault_pk, $vault, $field, $($check)*
+                        );
+                    )*
+                    $body
+                }
+                Ok(self)
+            }
+
+            pub fn load(
+                accounts: &[::solana_program::account_info::AccountInfo<'info>],
+            ) -> Result<Self, ::solana_program::program_error::ProgramError> {
+                let infos = $accounts::unpack(accounts)?;
+                $(
+                    let $field: $ty = $crate::loaders::vault_accounts!(
+                        @load infos.$field, $ty, [$($flag),*], $($loader)*
+                    );
+                )*
+                Self { $($field),* }.validate()
+            }
+        }
+    };
+
+    (@raw_ty $a:lifetime, $info:lifetime, optional $($rest:tt)*) => {
+        Option<&$a ::solana_program::account_info::AccountInfo<$info>>
+    };
+    (@raw_ty $a:lifetime, $info:lifetime, optional_try $($rest:tt)*) => {
+        Option<&$a ::solana_program::account_info::AccountInfo<$info>>
+    };
+    (@raw_ty $a:lifetime, $info:lifetime, $($rest:tt)*) => {
+        &$a ::solana_program::account_info::AccountInfo<$info>
+    };
+
+    (@raw $iter:ident, optional $($rest:tt)*) => { $iter.next() };
+    (@raw $iter:ident, optional_try $($rest:tt)*) => { $iter.next() };
+    (@raw $iter:ident, $($rest:tt)*) => {
+        ::solana_program::account_info::next_account_info($iter)?
+    };
+
+    (@info $wrapped:expr, optional $($rest:tt)*) => {
+        $wrapped.as_ref().map(AsRef::<::solana_program::account_info::AccountInfo>::as_ref)
+    };
+    (@info $wrapped:expr, optional_try $($rest:tt)*) => {
+        $wrapped.as_ref().map(AsRef::<::solana_program::account_info::AccountInfo>::as_ref)
+    };
+    (@info $wrapped:expr, $($rest:tt)*) => {
+        Some(AsRef::<::solana_program::account_info::AccountInfo>::as_ref(&$wrapped))
+    };
+
+    (@load $info:expr, $ty:ty, [$($flag:ident),*], optional($f:path)) => { $info.map($f) };
+    (@load $info:expr, $ty:ty, [$($flag:ident),*], optional_try($f:path)) => {
+        $info.map($f).transpose()?
+    };
+    (@load $info:expr, $ty:ty, [$($flag:ident),*], $($loader:tt)*) => {{
+        let info = $info;
+        $($crate::loaders::vault_accounts!(@flag info, $flag);)*
+        $crate::loaders::vault_accounts!(@wrap info, $ty, $($loader)*)
+    }};
+
+    (@flag $info:ident, writable) => {
+        $crate::utils::guards::require!(
+            $info.is_writable,
+            ::solana_program::program_error::ProgramError::InvalidArgument
+        )
+    };
+    (@flag $info:ident, signer) => {
+        $crate::utils::guards::require!(
+            $info.is_signer,
+            ::solana_program::program_error::ProgramError::MissingRequiredSignature
+        )
+    };
+
+    (@wrap $info:ident, $ty:ty, try_into) => { <$ty>::try_from($info)? };
+    (@wrap $info:ident, $ty:ty, readonly) => { <$ty>::readonly($info)? };
+    (@wrap $info:ident, $ty:ty, role($vault_info:ident, $role:ident)) => {
+        $crate::loaders::RequireRole::load($info, &$vault_info, $crate::state::Role::$role)?
+    };
+
+    (@checks $ctx:ident, $vault_pk:ident, $vault:ident, $field:ident, $(
+        $check:ident $(($($carg:tt)*))?
+    )&*) => {
+        $($crate::loaders::vault_accounts!(
+            @check $ctx, $vault_pk, $vault, $field, $check $(($($carg)*))?
+        );)*
+    };
+
+    (@check $ctx:ident, $vault_pk:ident, $vault:ident, $field:ident, has_one($vfield:ident)) => {
+        $crate::utils::guards::require_eq!(
+            &$vault.$vfield,
+            $ctx.$field.as_ref().key,
+            ::solana_program::program_error::ProgramError::InvalidArgument
+        )
+    };
+    (@check $ctx:ident, $vault_pk:ident, $vault:ident, $field:ident, mint($vfield:ident)) => {
+        $ctx.$field.check_mint(&$vault.$vfield)?
+    };
+    (@check $ctx:ident, $vault_pk:ident, $vault:ident, $field:ident, authority($other:ident)) => {
+        $ctx.$field.check_authority($ctx.$other.as_ref().key)?
+    };
+    (@check $ctx:ident, $vault_pk:ident, $vault:ident, $field:ident, vault_pda) => {
+        $ctx.$field.check_vault($vault_pk, &$vault)?
+    };
+}
+
+pub(crate) use vault_accounts;
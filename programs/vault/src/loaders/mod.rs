@@ -1,7 +1,17 @@
+pub mod authority_loaders;
+pub mod create_vault_loaders;
+mod macros;
+pub mod param_change_loaders;
+pub mod user_position_loaders;
 pub mod utils;
 pub mod vault_loaders;
 pub mod vault_with_fee_loaders;
 
+pub use authority_loaders::*;
+pub use create_vault_loaders::*;
+pub(crate) use macros::vault_accounts;
+pub use param_change_loaders::*;
+pub use user_position_loaders::*;
 pub use utils::*;
 pub use vault_loaders::*;
 pub use vault_with_fee_loaders::*;
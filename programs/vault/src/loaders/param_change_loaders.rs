@@ -0,0 +1,203 @@
+use std::{
+    cell::{Ref, RefMut},
+    mem::size_of,
+};
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    pending_change_seeds,
+    state::{create_pending_change_address, ParamKind, PendingChange, Role},
+    utils::guards::{require, require_eq, require_ge},
+};
+
+use super::{RequireRole, Signer, SystemProgramInfo, VaultInfo};
+
+pub struct PendingChangeInfo<'info> {
+    info: AccountInfo<'info>,
+}
+
+impl<'info> AsRef<AccountInfo<'info>> for PendingChangeInfo<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
+
+impl<'info> PendingChangeInfo<'info> {
+    /// Wrap an account without checking its content, see [`Self::check`]
+    pub fn unchecked(info: &AccountInfo<'info>) -> Self {
+        Self { info: info.clone() }
+    }
+
+    /// Checks that the account is a pending change of the vault `vault_pk`
+    pub fn check(&self, vault_pk: &Pubkey) -> ProgramResult {
+        let pending = self.get()?;
+        require_eq!(&pending.vault, vault_pk, ProgramError::InvalidArgument);
+
+        let expected_pk = create_pending_change_address(vault_pk, &pending)?;
+        require_eq!(self.info.key, &expected_pk, ProgramError::InvalidArgument);
+
+        Ok(())
+    }
+
+    fn check_data(&self, data: &[u8]) -> ProgramResult {
+        require_eq!(
+            self.info.owner,
+            &crate::ID,
+            crate::errors::VaultError::InvalidAccountOwner.into()
+        );
+        require_ge!(
+            data.len(),
+            size_of::<PendingChange>(),
+            ProgramError::AccountDataTooSmall
+        );
+        Ok(())
+    }
+
+    pub fn get(&self) -> Result<Ref<'_, PendingChange>, ProgramError> {
+        let data = self.info.try_borrow_data()?;
+        self.check_data(&data)?;
+        let res = Ref::map(data, |data| {
+            bytemuck::from_bytes::<PendingChange>(&data[0..size_of::<PendingChange>()])
+        });
+        require!(
+            res.is_initialized(),
+            crate::errors::VaultError::UninitializedAccount.into()
+        );
+        Ok(res)
+    }
+
+    /// Mutable access to an allocated pending change that is not initialized yet
+    pub fn get_uninitialized_mut(&self) -> Result<RefMut<'_, PendingChange>, ProgramError> {
+        let data = self.info.try_borrow_mut_data()?;
+        self.check_data(&data)?;
+        let res = RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut::<PendingChange>(&mut data[0..size_of::<PendingChange>()])
+        });
+        require!(
+            !res.is_initialized(),
+            crate::errors::VaultError::AlreadyInitialized.into()
+        );
+        Ok(res)
+    }
+}
+
+pub struct QueueParamChangeContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    // the new PDA pending change account
+    pub pending_change: PendingChangeInfo<'info>,
+    // vault admin
+    pub authority: RequireRole<'info>,
+    // pays for the rent of the pending change, refunded on execute or cancel
+    pub payer: Signer<'info>,
+    pub system_program: SystemProgramInfo<'info>,
+
+    pub pending_change_bump: u8,
+}
+
+impl<'info> QueueParamChangeContext<'info> {
+    pub fn validate(self) -> Result<Self, ProgramError> {
+        // -- only one change of each parameter can be pending
+        let pending_change = self.pending_change.as_ref();
+        require!(
+            pending_change.data_is_empty() && system_program::check_id(pending_change.owner),
+            crate::errors::VaultError::AlreadyInitialized.into()
+        );
+
+        Ok(self)
+    }
+
+    pub fn load(accounts: &[AccountInfo<'info>], kind: ParamKind) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        let vault_info: VaultInfo = next_account_info(iter)?.try_into()?;
+        let pending_change = next_account_info(iter)?;
+
+        let (expected_pk, pending_change_bump) = Pubkey::find_program_address(
+            pending_change_seeds!(vault_info.as_ref().key, kind as u8),
+            &crate::ID,
+        );
+        require_eq!(
+            pending_change.key,
+            &expected_pk,
+            ProgramError::InvalidArgument
+        );
+
+        Self {
+            pending_change: PendingChangeInfo::unchecked(pending_change),
+            authority: RequireRole::load(next_account_info(iter)?, &vault_info, Role::Admin)?,
+            vault_info,
+            payer: next_account_info(iter)?.try_into()?,
+            system_program: next_account_info(iter)?.try_into()?,
+            pending_change_bump,
+        }
+        .validate()
+    }
+}
+
+pub struct ExecuteParamChangeContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    pub pending_change: PendingChangeInfo<'info>,
+    // receives the rent of the closed pending change
+    pub rent_receiver: AccountInfo<'info>,
+}
+
+impl<'info> ExecuteParamChangeContext<'info> {
+    pub fn validate(self) -> Result<Self, ProgramError> {
+        self.pending_change.check(self.vault_info.as_ref().key)?;
+        require_eq!(
+            &self.pending_change.get()?.rent_receiver,
+            self.rent_receiver.key,
+            ProgramError::InvalidArgument
+        );
+        Ok(self)
+    }
+
+    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        Self {
+            vault_info: next_account_info(iter)?.try_into()?,
+            pending_change: PendingChangeInfo::unchecked(next_account_info(iter)?),
+            rent_receiver: next_account_info(iter)?.clone(),
+        }
+        .validate()
+    }
+}
+
+pub struct CancelParamChangeContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    pub pending_change: PendingChangeInfo<'info>,
+    // vault admin
+    pub authority: RequireRole<'info>,
+    // receives the rent of the closed pending change
+    pub rent_receiver: AccountInfo<'info>,
+}
+
+impl<'info> CancelParamChangeContext<'info> {
+    pub fn validate(self) -> Result<Self, ProgramError> {
+        self.pending_change.check(self.vault_info.as_ref().key)?;
+        require_eq!(
+            &self.pending_change.get()?.rent_receiver,
+            self.rent_receiver.key,
+            ProgramError::InvalidArgument
+        );
+        Ok(self)
+    }
+
+    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        let vault_info: VaultInfo = next_account_info(iter)?.try_into()?;
+        Self {
+            pending_change: PendingChangeInfo::unchecked(next_account_info(iter)?),
+            authority: RequireRole::load(next_account_info(iter)?, &vault_info, Role::Admin)?,
+            rent_receiver: next_account_info(iter)?.clone(),
+            vault_info,
+        }
+        .validate()
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    cell::{Ref, RefMut},
+    mem::size_of,
+};
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    state::{create_user_position_address, UserPosition},
+    user_position_seeds,
+    utils::guards::{require, require_eq, require_ge},
+};
+
+use super::{Signer, SystemProgramInfo, VaultInfo};
+
+pub struct UserPositionInfo<'info> {
+    info: AccountInfo<'info>,
+}
+
+impl<'info> AsRef<AccountInfo<'info>> for UserPositionInfo<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
+
+impl<'info> UserPositionInfo<'info> {
+    /// Wrap an account without checking its content, see [`Self::check`]
+    pub fn unchecked(info: &AccountInfo<'info>) -> Self {
+        Self { info: info.clone() }
+    }
+
+    /// Checks that the account is the position of `owner` in the vault `vault_pk`
+    pub fn check(&self, vault_pk: &Pubkey, owner: &Pubkey) -> ProgramResult {
+        let position = self.get()?;
+        require_eq!(&position.vault, vault_pk, ProgramError::InvalidArgument);
+        require_eq!(&position.owner, owner, ProgramError::InvalidArgument);
+
+        let expected_pk = create_user_position_address(vault_pk, &position)?;
+        require_eq!(self.info.key, &expected_pk, ProgramError::InvalidArgument);
+
+        Ok(())
+    }
+
+    fn check_data(&self, data: &[u8]) -> ProgramResult {
+        require_eq!(
+            self.info.owner,
+            &crate::ID,
+            crate::errors::VaultError::InvalidAccountOwner.into()
+        );
+        require_ge!(
+            data.len(),
+            size_of::<UserPosition>(),
+            ProgramError::AccountDataTooSmall
+        );
+        Ok(())
+    }
+
+    pub fn get(&self) -> Result<Ref<'_, UserPosition>, ProgramError> {
+        let data = self.info.try_borrow_data()?;
+        self.check_data(&data)?;
+        let res = Ref::map(data, |data| {
+            bytemuck::from_bytes::<UserPosition>(&data[0..size_of::<UserPosition>()])
+        });
+        require!(
+            res.is_initialized(),
+            crate::errors::VaultError::UninitializedAccount.into()
+        );
+        Ok(res)
+    }
+
+    pub fn get_mut(&self) -> Result<RefMut<'_, UserPosition>, ProgramError> {
+        let data = self.info.try_borrow_mut_data()?;
+        self.check_data(&data)?;
+        let res = RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut::<UserPosition>(&mut data[0..size_of::<UserPosition>()])
+        });
+        require!(
+            res.is_initialized(),
+            crate::errors::VaultError::UninitializedAccount.into()
+        );
+        Ok(res)
+    }
+
+    /// Mutable access to an allocated position account that is not initialized yet
+    pub fn get_uninitialized_mut(&self) -> Result<RefMut<'_, UserPosition>, ProgramError> {
+        let data = self.info.try_borrow_mut_data()?;
+        self.check_data(&data)?;
+        let res = RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut::<UserPosition>(&mut data[0..size_of::<UserPosition>()])
+        });
+        require!(
+            !res.is_initialized(),
+            crate::errors::VaultError::AlreadyInitialized.into()
+        );
+        Ok(res)
+    }
+}
+
+pub struct OpenPositionContext<'info> {
+    pub vault_info: VaultInfo<'info>,
+    // the new PDA position account
+    pub user_position: UserPositionInfo<'info>,
+    // owner of the position
+    pub owner: Signer<'info>,
+    // pays for the rent of the position account
+    pub payer: Signer<'info>,
+    pub system_program: SystemProgramInfo<'info>,
+
+    pub user_position_bump: u8,
+}
+
+impl<'info> OpenPositionContext<'info> {
+    pub fn validate(self) -> Result<Self, ProgramError> {
+        let user_position = self.user_position.as_ref();
+
+        // -- refuse to open a position that is already in use
+        require!(
+            user_position.data_is_empty() && system_program::check_id(user_position.owner),
+            crate::errors::VaultError::AlreadyInitialized.into()
+        );
+
+        Ok(self)
+    }
+
+    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
+        let iter = &mut accounts.iter();
+        let vault_info: VaultInfo = next_account_info(iter)?.try_into()?;
+        let user_position = next_account_info(iter)?;
+        let owner: Signer = next_account_info(iter)?.try_into()?;
+
+        let (expected_pk, user_position_bump) = Pubkey::find_program_address(
+            user_position_seeds!(vault_info.as_ref().key, owner.as_ref().key),
+            &crate::ID,
+        );
+        require_eq!(
+            user_position.key,
+            &expected_pk,
+            ProgramError::InvalidArgument
+        );
+
+        Self {
+            vault_info,
+            user_position: UserPositionInfo::unchecked(user_position),
+            owner,
+            payer: next_account_info(iter)?.try_into()?,
+            system_program: next_account_info(iter)?.try_into()?,
+            user_position_bump,
+        }
+        .validate()
+    }
+}
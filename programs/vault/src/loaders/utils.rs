@@ -1,6 +1,14 @@
 use {
-    crate::utils::guards::require,
-    solana_program::{account_info::AccountInfo, program_error::ProgramError},
+    super::VaultInfo,
+    crate::{
+        state::{Role, Vault},
+        utils::guards::{require, require_eq},
+    },
+    solana_program::{
+        account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+        program_option::COption, program_pack::Pack, pubkey::Pubkey,
+    },
+    spl_token::state::{Account as TokenAccount, Mint},
     std::result::Result,
 };
 
@@ -22,6 +30,156 @@ impl<'info> AsRef<AccountInfo<'info>> for Signer<'info> {
     }
 }
 
+/// Signer holding a role in the vault, see [`crate::state::Vault::has_role`]
+pub struct RequireRole<'info> {
+    pub info: AccountInfo<'info>,
+    pub role: Role,
+}
+
+impl<'info> RequireRole<'info> {
+    pub fn load(
+        info: &AccountInfo<'info>,
+        vault_info: &VaultInfo<'info>,
+        role: Role,
+    ) -> Result<Self, ProgramError> {
+        let vault = vault_info.get()?;
+        Self::check(info, &vault, role)
+    }
+
+    /// Same as [`Self::load`] for a vault stored in an older layout, see
+    /// [`VaultInfo::get_any_version`]
+    pub fn load_any_version(
+        info: &AccountInfo<'info>,
+        vault_info: &VaultInfo<'info>,
+        role: Role,
+    ) -> Result<Self, ProgramError> {
+        Self::check(info, &vault_info.get_any_version()?, role)
+    }
+
+    fn check(info: &AccountInfo<'info>, vault: &Vault, role: Role) -> Result<Self, ProgramError> {
+        let signer: Signer = info.try_into()?;
+        require!(
+            vault.has_role(signer.info.key, role),
+            crate::errors::VaultError::MissingRole.into()
+        );
+        Ok(Self {
+            info: signer.info,
+            role,
+        })
+    }
+}
+
+impl<'info> AsRef<AccountInfo<'info>> for RequireRole<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
+
+/// Checks that no account is passed twice, `None` stands for an absent
+/// optional account
+///
+/// Aliased accounts let an instruction cancel out its own token movements,
+/// such as a deposit from the vault assets account into itself, while the
+/// vault still books them.
+pub fn check_distinct_accounts(infos: &[Option<&AccountInfo>]) -> ProgramResult {
+    let keys = || infos.iter().flatten().map(|info| info.key);
+    for (i, key) in keys().enumerate() {
+        require!(
+            keys().skip(i + 1).all(|other| other != key),
+            crate::errors::VaultError::DuplicateAccount.into()
+        );
+    }
+    Ok(())
+}
+
+/// SPL token account, owned by the token program and writable
+///
+/// Use [`Self::readonly`] for accounts that are only read.
+pub struct TokenAccountInfo<'info> {
+    pub info: AccountInfo<'info>,
+    pub account: TokenAccount,
+}
+
+impl<'info> TokenAccountInfo<'info> {
+    pub fn readonly(info: &AccountInfo<'info>) -> Result<Self, ProgramError> {
+        require_eq!(info.owner, &spl_token::ID, ProgramError::IncorrectProgramId);
+        let account = TokenAccount::unpack(&info.try_borrow_data()?)?;
+        Ok(Self {
+            info: info.clone(),
+            account,
+        })
+    }
+
+    /// Checks that the account holds tokens of `mint`
+    pub fn check_mint(&self, mint: &Pubkey) -> ProgramResult {
+        require_eq!(&self.account.mint, mint, ProgramError::InvalidArgument);
+        Ok(())
+    }
+
+    /// Checks that `authority` may move the tokens, as the owner of the
+    /// account or as its SPL delegate
+    pub fn check_authority(&self, authority: &Pubkey) -> ProgramResult {
+        require!(
+            &self.account.owner == authority || self.account.delegate == COption::Some(*authority),
+            crate::errors::VaultError::InvalidAccountOwner.into()
+        );
+        Ok(())
+    }
+}
+
+impl<'info> TryFrom<&AccountInfo<'info>> for TokenAccountInfo<'info> {
+    type Error = ProgramError;
+    fn try_from(info: &AccountInfo<'info>) -> Result<Self, Self::Error> {
+        require!(info.is_writable, ProgramError::InvalidArgument);
+        Self::readonly(info)
+    }
+}
+
+impl<'info> AsRef<AccountInfo<'info>> for TokenAccountInfo<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
+
+/// SPL mint, owned by the token program and writable
+///
+/// Use [`Self::readonly`] for mints whose supply does not change.
+pub struct MintInfo<'info> {
+    pub info: AccountInfo<'info>,
+    pub mint: Mint,
+}
+
+impl<'info> MintInfo<'info> {
+    pub fn readonly(info: &AccountInfo<'info>) -> Result<Self, ProgramError> {
+        require_eq!(info.owner, &spl_token::ID, ProgramError::IncorrectProgramId);
+        let mint = Mint::unpack(&info.try_borrow_data()?)?;
+        Ok(Self {
+            info: info.clone(),
+            mint,
+        })
+    }
+
+    /// Checks that this is the mint `expected`
+    pub fn check_key(&self, expected: &Pubkey) -> ProgramResult {
+        require_eq!(self.info.key, expected, ProgramError::InvalidArgument);
+        Ok(())
+    }
+}
+
+impl<'info> TryFrom<&AccountInfo<'info>> for MintInfo<'info> {
+    type Error = ProgramError;
+    fn try_from(info: &AccountInfo<'info>) -> Result<Self, Self::Error> {
+        require!(info.is_writable, ProgramError::InvalidArgument);
+        Self::readonly(info)
+    }
+}
+
+impl<'info> AsRef<AccountInfo<'info>> for MintInfo<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
+
 pub struct SplTokenProgramInfo<'info> {
     pub info: AccountInfo<'info>,
 }
@@ -39,3 +197,24 @@ impl<'info> AsRef<AccountInfo<'info>> for SplTokenProgramInfo<'info> {
         &self.info
     }
 }
+
+pub struct SystemProgramInfo<'info> {
+    pub info: AccountInfo<'info>,
+}
+
+impl<'info> TryFrom<&AccountInfo<'info>> for SystemProgramInfo<'info> {
+    type Error = ProgramError;
+    fn try_from(info: &AccountInfo<'info>) -> Result<Self, Self::Error> {
+        require!(
+            solana_program::system_program::check_id(info.key),
+            ProgramError::IncorrectProgramId
+        );
+        Ok(Self { info: info.clone() })
+    }
+}
+
+impl<'info> AsRef<AccountInfo<'info>> for SystemProgramInfo<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
@@ -11,11 +11,18 @@ use solana_program::{
 };
 
 use crate::{
-    state::{create_vault_assets_account_address, Vault},
-    utils::guards::require_eq,
+    operations::vault_load_any_version,
+    state::{
+        create_shares_mint_authority_address, create_vault_assets_account_address,
+        vault_data_version, Role, Vault, VAULT_VERSION,
+    },
+    utils::guards::{require, require_eq, require_ge},
 };
 
-use super::{Signer, SplTokenProgramInfo};
+use super::{
+    vault_accounts, MintInfo, RequireRole, Signer, SplTokenProgramInfo, SystemProgramInfo,
+    TokenAccountInfo, UserPositionInfo,
+};
 
 pub struct VaultInfo<'info> {
     info: AccountInfo<'info>,
@@ -31,20 +38,50 @@ impl<'info> TryFrom<&AccountInfo<'info>> for VaultInfo<'info> {
     type Error = ProgramError;
 
     fn try_from(info: &AccountInfo<'info>) -> Result<Self, Self::Error> {
-        // owned by vault program
-        // has discriminant
         Self { info: info.clone() }.validate()
     }
 }
 
 impl<'info> VaultInfo<'info> {
+    /// Wrap an account without checking its content, e.g., during creation or migration
+    pub fn unchecked(info: &AccountInfo<'info>) -> Self {
+        Self { info: info.clone() }
+    }
+
     pub fn validate(self) -> Result<Self, ProgramError> {
         self.get()?.validate()?;
         Ok(self)
     }
 
+    fn check_owner(&self) -> ProgramResult {
+        require_eq!(
+            self.info.owner,
+            &crate::ID,
+            crate::errors::VaultError::InvalidAccountOwner.into()
+        );
+        Ok(())
+    }
+
+    /// Checks that `data` holds a vault in the current layout
+    fn check_data(data: &[u8]) -> ProgramResult {
+        let version = vault_data_version(data)?;
+        require_eq!(
+            version,
+            VAULT_VERSION,
+            crate::errors::VaultError::MigrationRequired.into()
+        );
+        require_ge!(
+            data.len(),
+            size_of::<Vault>(),
+            ProgramError::AccountDataTooSmall
+        );
+        Ok(())
+    }
+
     pub fn get(&self) -> Result<Ref<'_, Vault>, ProgramError> {
+        self.check_owner()?;
         let data = self.info.try_borrow_data()?;
+        Self::check_data(&data)?;
         let res = Ref::map(data, |data| {
             bytemuck::from_bytes::<Vault>(&data[0..size_of::<Vault>()])
         });
@@ -52,16 +89,45 @@ impl<'info> VaultInfo<'info> {
     }
 
     pub fn get_mut(&self) -> Result<RefMut<'_, Vault>, ProgramError> {
+        self.check_owner()?;
+        let data = self.info.try_borrow_mut_data()?;
+        Self::check_data(&data)?;
+        let res = RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut::<Vault>(&mut data[0..size_of::<Vault>()])
+        });
+        Ok(res)
+    }
+
+    /// Mutable access to an allocated vault account that is not initialized yet
+    pub fn get_uninitialized_mut(&self) -> Result<RefMut<'_, Vault>, ProgramError> {
+        self.check_owner()?;
         let data = self.info.try_borrow_mut_data()?;
+        require_ge!(
+            data.len(),
+            size_of::<Vault>(),
+            ProgramError::AccountDataTooSmall
+        );
         let res = RefMut::map(data, |data| {
             bytemuck::from_bytes_mut::<Vault>(&mut data[0..size_of::<Vault>()])
         });
+        require!(
+            !res.is_initialized(),
+            crate::errors::VaultError::AlreadyInitialized.into()
+        );
         Ok(res)
     }
+
+    /// Reads a vault stored in any supported layout version, upgraded to the current layout
+    pub fn get_any_version(&self) -> Result<Vault, ProgramError> {
+        self.check_owner()?;
+        let data = self.info.try_borrow_data()?;
+        Ok(vault_load_any_version(&data)?)
+    }
 }
 
+/// Token account holding the vault assets
 pub struct VaultAssetsAccount<'info> {
-    pub info: AccountInfo<'info>,
+    pub account: TokenAccountInfo<'info>,
 }
 
 impl<'info> VaultAssetsAccount<'info> {
@@ -69,203 +135,483 @@ impl<'info> VaultAssetsAccount<'info> {
         Ok(self)
     }
 
+    /// Wrap an account that is only read, such as by `process_update_reward`
+    pub fn readonly(info: &AccountInfo<'info>) -> Result<Self, ProgramError> {
+        Self {
+            account: TokenAccountInfo::readonly(info)?,
+        }
+        .validate()
+    }
+
+    /// Checks that the account is the assets PDA of the vault `vault_pk`
     pub fn check_vault(&self, vault_pk: &Pubkey, vault: &Vault) -> ProgramResult {
+        let info = self.account.as_ref();
+        require_eq!(
+            &vault.vault_assets_account,
+            info.key,
+            ProgramError::InvalidArgument
+        );
+
         let expected_pk = create_vault_assets_account_address(vault_pk, vault)?;
-        require_eq!(self.info.key, &expected_pk, ProgramError::InvalidArgument);
-        Ok(())
+        require_eq!(info.key, &expected_pk, ProgramError::InvalidArgument);
+
+        self.account.check_mint(&vault.assets_mint)
     }
 }
 
 impl<'info> AsRef<AccountInfo<'info>> for VaultAssetsAccount<'info> {
     fn as_ref(&self) -> &AccountInfo<'info> {
-        &self.info
+        self.account.as_ref()
     }
 }
 
 impl<'info> TryFrom<&AccountInfo<'info>> for VaultAssetsAccount<'info> {
     type Error = ProgramError;
     fn try_from(info: &AccountInfo<'info>) -> Result<Self, Self::Error> {
-        Self { info: info.clone() }.validate()
+        Self {
+            account: info.try_into()?,
+        }
+        .validate()
     }
 }
 
-pub struct DepositContext<'info> {
-    // the vault
-    pub vault_info: VaultInfo<'info>,
-    // token account of the vault deposit
-    pub vault_assets_account: VaultAssetsAccount<'info>,
-    // mint for assets token
-    pub assets_mint: AccountInfo<'info>,
-    pub shares_mint: AccountInfo<'info>,
-    // token account for the user making a deposit
-    pub user_assets_account: AccountInfo<'info>,
-    // signing authority for the user assets account
-    pub authority: Signer<'info>,
-    pub user_shares_account: AccountInfo<'info>,
-    // SPL token program to make the transfer
-    pub spl_token_program: SplTokenProgramInfo<'info>,
+/// PDA mint authority of the shares mint
+pub struct SharesMintAuthority<'info> {
+    pub info: AccountInfo<'info>,
 }
 
-impl<'info> DepositContext<'info> {
-    pub fn validate(self) -> Result<Self, ProgramError> {
-        let vault = self.vault_info.get()?;
-        require_eq!(
-            &vault.assets_mint,
-            self.assets_mint.key,
-            ProgramError::InvalidArgument
-        );
-
-        require_eq!(
-            &vault.shares_mint,
-            self.shares_mint.key,
-            ProgramError::InvalidArgument
-        );
-
-        require_eq!(
-            &vault.vault_assets_account,
-            self.vault_assets_account.as_ref().key,
-            ProgramError::InvalidArgument
-        );
+impl<'info> SharesMintAuthority<'info> {
+    /// Checks that the account is the shares mint authority of the vault `vault_pk`
+    pub fn check_vault(&self, vault_pk: &Pubkey, vault: &Vault) -> ProgramResult {
+        let expected_pk = create_shares_mint_authority_address(vault_pk, vault)?;
+        require_eq!(self.info.key, &expected_pk, ProgramError::InvalidArgument);
+        Ok(())
+    }
+}
 
-        self.vault_assets_account
-            .check_vault(self.vault_info.as_ref().key, &vault)?;
+impl<'info> AsRef<AccountInfo<'info>> for SharesMintAuthority<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        &self.info
+    }
+}
 
-        drop(vault);
-        Ok(self)
+impl<'info> TryFrom<&AccountInfo<'info>> for SharesMintAuthority<'info> {
+    type Error = ProgramError;
+    fn try_from(info: &AccountInfo<'info>) -> Result<Self, Self::Error> {
+        Ok(Self { info: info.clone() })
     }
+}
 
-    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
-        let iter = &mut accounts.iter();
-        Self {
-            vault_info: next_account_info(iter)?.try_into()?,
-            vault_assets_account: next_account_info(iter)?.try_into()?,
-            assets_mint: next_account_info(iter)?.clone(),
-            shares_mint: next_account_info(iter)?.clone(),
-            user_assets_account: next_account_info(iter)?.clone(),
-            authority: next_account_info(iter)?.try_into()?,
-            user_shares_account: next_account_info(iter)?.clone(),
-            spl_token_program: next_account_info(iter)?.try_into()?,
+/// Checks the position passed to a deposit
+///
+/// The position belongs to the owner of the shares account receiving the
+/// deposit. While the vault has a holding period it is required, so that
+/// every deposit restarts the holding period of the receiver and fresh
+/// shares never get the reduced exit fee.
+pub fn check_receiver_position(
+    user_position: Option<&UserPositionInfo>,
+    user_shares_account: &TokenAccountInfo,
+    vault_pk: &Pubkey,
+    vault: &Vault,
+) -> ProgramResult {
+    match user_position {
+        Some(user_position) => user_position.check(vault_pk, &user_shares_account.account.owner),
+        None => {
+            require_eq!(
+                i64::from(vault.exit_fee_holding_period),
+                0,
+                ProgramError::NotEnoughAccountKeys
+            );
+            Ok(())
         }
-        .validate()
     }
 }
 
-pub struct RedeemSharesContext<'info> {
-    pub vault_info: VaultInfo<'info>,
-    pub vault_assets_account: AccountInfo<'info>,
-    pub assets_mint: AccountInfo<'info>,
-    pub shares_mint: AccountInfo<'info>,
-    pub user_shares_account: AccountInfo<'info>,
-    pub authority: Signer<'info>,
-    pub user_assets_account: AccountInfo<'info>,
-    pub spl_token_program: SplTokenProgramInfo<'info>,
+vault_accounts! {
+    pub struct DepositContext, DepositAccounts {
+        // the vault
+        vault_info: VaultInfo<'info> = try_into [writable],
+        // token account of the vault deposit
+        vault_assets_account: VaultAssetsAccount<'info> = try_into if vault_pda,
+        // mint for assets token
+        assets_mint: MintInfo<'info> = readonly if has_one(assets_mint),
+        shares_mint: MintInfo<'info> = try_into if has_one(shares_mint),
+        // token account for the user making a deposit
+        user_assets_account: TokenAccountInfo<'info> = try_into
+            if mint(assets_mint) & authority(authority),
+        // owner or delegate of the user assets account
+        authority: Signer<'info> = try_into,
+        // receiver of the minted shares, any token account of the shares mint
+        user_shares_account: TokenAccountInfo<'info> = try_into if mint(shares_mint),
+        // SPL token program to make the transfer
+        spl_token_program: SplTokenProgramInfo<'info> = try_into,
+        shares_mint_authority: SharesMintAuthority<'info> = try_into if vault_pda,
+        // position of the receiver, restarts its exit fee holding period,
+        // required while the vault has a holding period
+        user_position: Option<UserPositionInfo<'info>> = optional(UserPositionInfo::unchecked),
+    }
+    validate(ctx, vault_pk, vault) {
+        check_receiver_position(
+            ctx.user_position.as_ref(),
+            &ctx.user_shares_account,
+            vault_pk,
+            &vault,
+        )?;
+    }
 }
 
-impl<'info> RedeemSharesContext<'info> {
-    pub fn validate(self) -> Result<Self, ProgramError> {
-        let vault = self.vault_info.get()?;
-        require_eq!(
-            &vault.assets_mint,
-            self.assets_mint.key,
-            ProgramError::InvalidArgument
-        );
+/// Accounts of `process_mint_shares_exact`, same as a deposit
+pub type MintSharesContext<'info> = DepositContext<'info>;
+
+vault_accounts! {
+    pub struct RedeemSharesContext, RedeemSharesAccounts {
+        vault_info: VaultInfo<'info> = try_into [writable],
+        vault_assets_account: VaultAssetsAccount<'info> = try_into if vault_pda,
+        assets_mint: MintInfo<'info> = readonly if has_one(assets_mint),
+        shares_mint: MintInfo<'info> = try_into if has_one(shares_mint),
+        // shares to burn, owned by the authority or delegated to it
+        user_shares_account: TokenAccountInfo<'info> = try_into
+            if mint(shares_mint) & authority(authority),
+        // owner or delegate of the user shares account
+        authority: Signer<'info> = try_into,
+        // receiver of the assets, any token account of the assets mint
+        user_assets_account: TokenAccountInfo<'info> = try_into if mint(assets_mint),
+        spl_token_program: SplTokenProgramInfo<'info> = try_into,
+        // optional position of the shares owner, for the reduced exit fee
+        user_position: Option<UserPositionInfo<'info>> = optional(UserPositionInfo::unchecked),
+    }
+    validate(ctx, vault_pk, _vault) {
+        if let Some(user_position) = &ctx.user_position {
+            user_position.check(vault_pk, &ctx.user_shares_account.account.owner)?;
+        }
+    }
+}
 
-        require_eq!(
-            &vault.shares_mint,
-            self.shares_mint.key,
-            ProgramError::InvalidArgument
-        );
+/// Accounts of `process_withdraw_assets`, same as a redeem
+pub type WithdrawAssetsContext<'info> = RedeemSharesContext<'info>;
 
-        require_eq!(
-            &vault.vault_assets_account,
-            self.vault_assets_account.key,
-            ProgramError::InvalidArgument
-        );
+vault_accounts! {
+    pub struct UpdateRewardContext, UpdateRewardAccounts {
+        vault_info: VaultInfo<'info> = try_into [writable],
+        vault_assets_account: VaultAssetsAccount<'info> = readonly if vault_pda,
+        // holder of the reward updater role
+        authority: RequireRole<'info> = role(vault_info, RewardUpdater),
+    }
+}
 
-        drop(vault);
-        Ok(self)
+vault_accounts! {
+    pub struct SlashContext, SlashAccounts {
+        vault_info: VaultInfo<'info> = try_into [writable],
+        vault_assets_account: VaultAssetsAccount<'info> = try_into if vault_pda,
+        // receives the slashed assets
+        user_token_account: TokenAccountInfo<'info> = try_into if mint(assets_mint),
+        assets_mint: MintInfo<'info> = readonly if has_one(assets_mint),
+        // holder of the slasher role
+        authority: RequireRole<'info> = role(vault_info, Slasher),
+        spl_token_program: SplTokenProgramInfo<'info> = try_into,
     }
+}
+
+pub struct MigrateVaultContext<'info> {
+    // the vault to migrate, possibly stored in an older layout
+    pub vault_info: VaultInfo<'info>,
+    // vault admin
+    pub authority: RequireRole<'info>,
+    // pays for the rent of the extended account
+    pub payer: Signer<'info>,
+    pub system_program: SystemProgramInfo<'info>,
+}
+
+impl<'info> MigrateVaultContext<'info> {
     pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
         let iter = &mut accounts.iter();
-        Self {
-            vault_info: next_account_info(iter)?.try_into()?,
-            vault_assets_account: next_account_info(iter)?.clone(),
-            assets_mint: next_account_info(iter)?.clone(),
-            shares_mint: next_account_info(iter)?.clone(),
-            user_shares_account: next_account_info(iter)?.clone(),
-            authority: next_account_info(iter)?.try_into()?,
-            user_assets_account: next_account_info(iter)?.clone(),
-            spl_token_program: next_account_info(iter)?.try_into()?,
-        }
-        .validate()
+        let vault_info = VaultInfo::unchecked(next_account_info(iter)?);
+        Ok(Self {
+            authority: RequireRole::load_any_version(
+                next_account_info(iter)?,
+                &vault_info,
+                Role::Admin,
+            )?,
+            vault_info,
+            payer: next_account_info(iter)?.try_into()?,
+            system_program: next_account_info(iter)?.try_into()?,
+        })
     }
 }
 
-pub struct UpdateRewardContext<'info> {
-    pub vault_info: VaultInfo<'info>,
-    pub vault_assets_account: AccountInfo<'info>,
+vault_accounts! {
+    pub struct PreviewContext, PreviewAccounts {
+        // the vault to quote
+        vault_info: VaultInfo<'info> = try_into,
+        // shares account of the owner, only needed by `PreviewKind::MaxRedeem`
+        user_shares_account: Option<TokenAccountInfo<'info>> =
+            optional_try(TokenAccountInfo::readonly),
+    }
+    validate(ctx, _vault_pk, vault) {
+        if let Some(user_shares_account) = &ctx.user_shares_account {
+            user_shares_account.check_mint(&vault.shares_mint)?;
+        }
+    }
 }
 
-impl<'info> UpdateRewardContext<'info> {
-    pub fn validate(self) -> Result<Self, ProgramError> {
-        let vault = self.vault_info.get()?;
-        require_eq!(
-            &vault.vault_assets_account,
-            self.vault_assets_account.key,
-            ProgramError::InvalidArgument
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        loaders::check_distinct_accounts,
+        state::{UserPosition, USER_POSITION_DISCRIMINATOR, VAULT_DISCRIMINATOR},
+        user_position_seeds, VaultError,
+    };
+    use solana_program::{program_option::COption, program_pack::Pack};
+    use spl_token::state::{Account as TokenAccount, AccountState};
+
+    fn vault_bytes(discriminator: [u8; 8]) -> Vec<u8> {
+        let vault = Vault {
+            discriminator,
+            version: VAULT_VERSION,
+            assets_mint: Pubkey::new_unique(),
+            shares_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        bytemuck::bytes_of(&vault).to_vec()
+    }
+
+    fn load_vault(owner: &Pubkey, data: &mut [u8]) -> Result<(), ProgramError> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, data, owner, false, 0);
+        VaultInfo::try_from(&info).map(|_| ())
+    }
+
+    #[test]
+    fn test_vault_info_ok() {
+        let mut data = vault_bytes(VAULT_DISCRIMINATOR);
+        assert!(load_vault(&crate::ID, &mut data).is_ok());
+    }
+
+    #[test]
+    fn test_vault_info_wrong_owner() {
+        let mut data = vault_bytes(VAULT_DISCRIMINATOR);
+        assert_eq!(
+            load_vault(&Pubkey::new_unique(), &mut data),
+            Err(VaultError::InvalidAccountOwner.into())
         );
+    }
 
-        drop(vault);
-        Ok(self)
+    #[test]
+    fn test_vault_info_wrong_size() {
+        let mut data = vault_bytes(VAULT_DISCRIMINATOR);
+        let len = data.len();
+        assert_eq!(
+            load_vault(&crate::ID, &mut data[..len - 1]),
+            Err(ProgramError::AccountDataTooSmall)
+        );
     }
-}
 
-impl<'info> UpdateRewardContext<'info> {
-    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
-        let iter = &mut accounts.iter();
-        Self {
-            vault_info: next_account_info(iter)?.try_into()?,
-            vault_assets_account: next_account_info(iter)?.clone(),
-        }
-        .validate()
+    #[test]
+    fn test_vault_info_wrong_discriminator() {
+        let mut data = vault_bytes(*b"notvault");
+        assert_eq!(
+            load_vault(&crate::ID, &mut data),
+            Err(VaultError::InvalidDiscriminator.into())
+        );
     }
-}
 
-pub struct SlashContext<'info> {
-    pub vault_info: VaultInfo<'info>,
-    pub vault_assets_account: VaultAssetsAccount<'info>,
-    pub user_token_account: AccountInfo<'info>,
-    pub assets_mint: AccountInfo<'info>,
-    pub authority: Signer<'info>,
-    pub spl_token_program: SplTokenProgramInfo<'info>,
-}
+    #[test]
+    fn test_vault_info_uninitialized() {
+        let mut data = vec![0u8; size_of::<Vault>()];
+        assert_eq!(
+            load_vault(&crate::ID, &mut data),
+            Err(VaultError::UninitializedAccount.into())
+        );
+    }
 
-impl<'info> SlashContext<'info> {
-    pub fn validate(self) -> Result<Self, ProgramError> {
-        let vault_pk = self.vault_info.as_ref().key;
-        let vault = self.vault_info.get()?;
+    #[test]
+    fn test_token_account_owner_or_delegate() {
+        let (mint, owner, delegate) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let account = TokenAccount {
+            mint,
+            owner,
+            delegate: COption::Some(delegate),
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; TokenAccount::LEN];
+        account.pack_into_slice(&mut data);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let loaded = TokenAccountInfo::try_from(&info).unwrap();
+        assert!(loaded.check_mint(&mint).is_ok());
+        assert_eq!(
+            loaded.check_mint(&Pubkey::new_unique()),
+            Err(ProgramError::InvalidArgument)
+        );
 
-        require_eq!(
-            &vault.slash_admin,
-            self.authority.as_ref().key,
-            ProgramError::InvalidArgument
+        assert!(loaded.check_authority(&owner).is_ok());
+        assert!(loaded.check_authority(&delegate).is_ok());
+        assert_eq!(
+            loaded.check_authority(&Pubkey::new_unique()),
+            Err(VaultError::InvalidAccountOwner.into())
         );
 
-        self.vault_assets_account.check_vault(vault_pk, &vault)?;
+        // -- token accounts are writable unless loaded as read-only
+        let mut info = info.clone();
+        info.is_writable = false;
+        assert!(TokenAccountInfo::try_from(&info).is_err());
+        assert!(TokenAccountInfo::readonly(&info).is_ok());
+    }
 
-        drop(vault);
-        Ok(self)
+    #[test]
+    fn test_accounts_unpack_in_order() {
+        let keys: Vec<Pubkey> = (0..9).map(|_| Pubkey::new_unique()).collect();
+        let mut lamports = vec![0u64; keys.len()];
+        let mut data = vec![[0u8; 0]; keys.len()];
+        let infos: Vec<AccountInfo> = keys
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((key, lamports), data)| {
+                AccountInfo::new(key, false, true, lamports, data, &crate::ID, false, 0)
+            })
+            .collect();
+
+        assert_eq!(RedeemSharesAccounts::LEN, infos.len());
+        let accounts = RedeemSharesAccounts::unpack(&infos[..8]).unwrap();
+        assert_eq!(accounts.user_shares_account.key, &keys[4]);
+        assert_eq!(accounts.user_assets_account.key, &keys[6]);
+        assert!(accounts.user_position.is_none());
+
+        let accounts = RedeemSharesAccounts::unpack(&infos).unwrap();
+        assert_eq!(accounts.user_position.map(|info| info.key), Some(&keys[8]));
+
+        assert_eq!(
+            RedeemSharesAccounts::unpack(&infos[..7]).err(),
+            Some(ProgramError::NotEnoughAccountKeys)
+        );
     }
-    pub fn load(accounts: &[AccountInfo<'info>]) -> Result<Self, ProgramError> {
-        let iter = &mut accounts.iter();
-        Self {
-            vault_info: next_account_info(iter)?.try_into()?,
-            vault_assets_account: next_account_info(iter)?.try_into()?,
-            user_token_account: next_account_info(iter)?.clone(),
-            assets_mint: next_account_info(iter)?.clone(),
-            authority: next_account_info(iter)?.try_into()?,
-            spl_token_program: next_account_info(iter)?.try_into()?,
-        }
-        .validate()
+
+    #[test]
+    fn test_distinct_accounts() {
+        let (key, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports, mut data) = ([0u64; 2], [[0u8; 0]; 2]);
+        let [lamports_a, lamports_b] = &mut lamports;
+        let [data_a, data_b] = &mut data;
+        let info = AccountInfo::new(&key, false, true, lamports_a, data_a, &crate::ID, false, 0);
+        let other = AccountInfo::new(
+            &other,
+            false,
+            true,
+            lamports_b,
+            data_b,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        assert!(check_distinct_accounts(&[Some(&info), Some(&other), None, None]).is_ok());
+        assert_eq!(
+            check_distinct_accounts(&[Some(&info), Some(&other), Some(&info)]),
+            Err(VaultError::DuplicateAccount.into())
+        );
+    }
+
+    #[test]
+    fn test_receiver_position_required_with_holding_period() {
+        let (vault_pk, receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let account = TokenAccount {
+            owner: receiver,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; TokenAccount::LEN];
+        account.pack_into_slice(&mut data);
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &spl_token::ID,
+            false,
+            0,
+        );
+        let shares_account = TokenAccountInfo::try_from(&info).unwrap();
+
+        let position = |owner: Pubkey| {
+            let (key, bump) =
+                Pubkey::find_program_address(user_position_seeds!(vault_pk, owner), &crate::ID);
+            let position = UserPosition {
+                discriminator: USER_POSITION_DISCRIMINATOR,
+                vault: vault_pk,
+                owner,
+                bump,
+                ..Default::default()
+            };
+            (key, bytemuck::bytes_of(&position).to_vec())
+        };
+        let (receiver_key, mut receiver_data) = position(receiver);
+        let (other_key, mut other_data) = position(Pubkey::new_unique());
+        let (mut lamports_a, mut lamports_b) = (0, 0);
+        let receiver_info = AccountInfo::new(
+            &receiver_key,
+            false,
+            true,
+            &mut lamports_a,
+            &mut receiver_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let other_info = AccountInfo::new(
+            &other_key,
+            false,
+            true,
+            &mut lamports_b,
+            &mut other_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let receiver_position = UserPositionInfo::unchecked(&receiver_info);
+        let other_position = UserPositionInfo::unchecked(&other_info);
+
+        // -- without a holding period the position is optional
+        let mut vault = Vault::default();
+        assert!(check_receiver_position(None, &shares_account, &vault_pk, &vault).is_ok());
+
+        // -- with one, leaving it out would keep the old deposit timestamp
+        vault.exit_fee_holding_period = 3600.into();
+        assert_eq!(
+            check_receiver_position(None, &shares_account, &vault_pk, &vault),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+        assert!(check_receiver_position(
+            Some(&receiver_position),
+            &shares_account,
+            &vault_pk,
+            &vault
+        )
+        .is_ok());
+        assert_eq!(
+            check_receiver_position(Some(&other_position), &shares_account, &vault_pk, &vault),
+            Err(ProgramError::InvalidArgument)
+        );
     }
 }
@@ -1,7 +1,21 @@
+pub mod vault_authority_operations;
 pub mod vault_exact_deposit_operations;
 pub mod vault_fee_operations;
+pub mod vault_init_operations;
+pub mod vault_migrate_operations;
 pub mod vault_operations;
+pub mod vault_param_operations;
+pub mod vault_role_operations;
+pub mod vault_slippage_operations;
+pub mod vault_status_operations;
 
+pub use vault_authority_operations::*;
 pub use vault_exact_deposit_operations::*;
 pub use vault_fee_operations::*;
+pub use vault_init_operations::*;
+pub use vault_migrate_operations::*;
 pub use vault_operations::*;
+pub use vault_param_operations::*;
+pub use vault_role_operations::*;
+pub use vault_slippage_operations::*;
+pub use vault_status_operations::*;
@@ -0,0 +1,53 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    state::{AuthorityRole, Vault, VaultAction},
+    utils::guards::{require, require_ne},
+    VaultResult,
+};
+
+fn pending_authority_mut(vault: &mut Vault, role: AuthorityRole) -> &mut Pubkey {
+    match role {
+        AuthorityRole::Admin => &mut vault.pending_admin,
+        AuthorityRole::SlashAdmin => &mut vault.pending_slash_admin,
+    }
+}
+
+/// Proposes `new_authority` as the next holder of `role`, replacing any
+/// earlier proposal
+pub fn vault_propose_authority(
+    vault: &mut Vault,
+    role: AuthorityRole,
+    new_authority: Pubkey,
+) -> VaultResult<()> {
+    vault.require_allows(VaultAction::Configure)?;
+    require_ne!(
+        new_authority,
+        Pubkey::default(),
+        crate::errors::VaultError::GuardFail
+    );
+    *pending_authority_mut(vault, role) = new_authority;
+    Ok(())
+}
+
+/// Hands `role` over to the proposed key
+pub fn vault_accept_authority(vault: &mut Vault, role: AuthorityRole) -> VaultResult<()> {
+    vault.require_allows(VaultAction::Configure)?;
+    let pending = vault.pending_authority(role).copied();
+    require!(pending.is_some(), crate::errors::VaultError::GuardFail);
+
+    let pending = pending.unwrap_or_default();
+    match role {
+        AuthorityRole::Admin => vault.admin = pending,
+        AuthorityRole::SlashAdmin => vault.slash_admin = pending,
+    }
+    *pending_authority_mut(vault, role) = Pubkey::default();
+    Ok(())
+}
+
+/// Drops the pending proposal for `role`, if any
+pub fn vault_cancel_authority(vault: &mut Vault, role: AuthorityRole) -> VaultResult<()> {
+    vault.require_allows(VaultAction::Configure)?;
+    *pending_authority_mut(vault, role) = Pubkey::default();
+    Ok(())
+}
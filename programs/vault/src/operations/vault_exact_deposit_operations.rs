@@ -1,8 +1,13 @@
-use crate::{state::Vault, utils::math::FeeBps, VaultResult};
+use crate::{
+    state::{Vault, VaultAction},
+    utils::math::FeeBps,
+    VaultResult,
+};
 
 use super::VaultEffect;
 
 pub fn vault_deposit_assets_exact(vault: &mut Vault, tkn_amt: u64) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Deposit)?;
     let shares_to_user = vault.convert_assets_to_shares(tkn_amt)?;
     let assets_to_vault = vault.convert_shares_to_assets(shares_to_user)?;
 
@@ -20,6 +25,7 @@ pub fn vault_deposit_assets_with_fee_exact(
     vault: &mut Vault,
     tkn_amt: u64,
 ) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Deposit)?;
     let fee_bps: FeeBps = vault.fee_in_bps()?;
     // -- maximum possible fee
     let gross = fee_bps.apply(tkn_amt)?;
@@ -30,6 +36,10 @@ pub fn vault_deposit_assets_with_fee_exact(
     // -- compute fee based on actual use
     let actual_gross = fee_bps.apply(assets_to_vault)?;
 
+    vault.mint_shares(shares_to_user)?;
+    vault.add_token(assets_to_vault)?;
+    vault.add_fee(actual_gross.fee)?;
+
     Ok(VaultEffect {
         shares_to_user,
         assets_to_vault,
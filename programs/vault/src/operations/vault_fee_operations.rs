@@ -1,8 +1,13 @@
-use crate::{state::Vault, utils::math::FeeBps, VaultResult};
+use crate::{
+    state::{Vault, VaultAction, VaultStatus},
+    utils::math::FeeBps,
+    VaultResult,
+};
 
 use super::VaultEffect;
 
 pub fn vault_deposit_assets_with_fee(vault: &mut Vault, tkn_amt: u64) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Deposit)?;
     let fee_bps: FeeBps = vault.fee_in_bps()?;
     let gross = fee_bps.apply(tkn_amt)?;
 
@@ -10,6 +15,7 @@ pub fn vault_deposit_assets_with_fee(vault: &mut Vault, tkn_amt: u64) -> VaultRe
 
     vault.mint_shares(shares_to_user)?;
     vault.add_token(gross.net_amount)?;
+    vault.add_fee(gross.fee)?;
 
     Ok(VaultEffect {
         shares_to_user,
@@ -19,7 +25,67 @@ pub fn vault_deposit_assets_with_fee(vault: &mut Vault, tkn_amt: u64) -> VaultRe
     })
 }
 
+/// Accrues the management fee from the last accrual up to `now`
+///
+/// The fee is taken from depositor assets into `fee_amount`, capped so that
+/// the vault stays solvent. Must run before any conversion between shares and
+/// assets. No fee accrues while the vault is fully paused.
+pub fn vault_accrue_management_fee(vault: &mut Vault, now: i64) -> VaultResult<VaultEffect> {
+    let last_ts = vault.last_fee_accrual_ts();
+    if now <= last_ts {
+        return Ok(VaultEffect::default());
+    }
+    vault.last_fee_accrual_ts = now.into();
+    if vault.status()? == VaultStatus::FullyPaused {
+        return Ok(VaultEffect::default());
+    }
+
+    let elapsed = now.abs_diff(last_ts);
+    let gross = vault
+        .management_fee_in_bps()?
+        .apply_annual(vault.num_assets(), elapsed)?;
+
+    // -- keep enough assets to back every share
+    let min_assets = vault.num_shares().div_ceil(vault.virtual_shares_offset());
+    let assets_to_fee = gross.fee.min(vault.num_assets().saturating_sub(min_assets));
+
+    if assets_to_fee > 0 {
+        vault.del_token(assets_to_fee)?;
+        vault.add_fee(assets_to_fee)?;
+    }
+
+    Ok(VaultEffect {
+        assets_to_fee,
+        ..Default::default()
+    })
+}
+
+/// Charges the performance fee on a `reward` already added to the vault
+///
+/// Only the part of the reward that lifts the share price above the
+/// high-water mark is charged. The mark then follows the price up, and is
+/// left in place when the price goes down, e.g., after a slash.
+pub fn vault_charge_performance_fee(vault: &mut Vault, reward: u64) -> VaultResult<u64> {
+    // -- a mark too high to be represented leaves no gain
+    let mark_value = vault.high_water_mark_value().unwrap_or(u64::MAX);
+    let gain = vault
+        .virtual_assets()?
+        .saturating_sub(mark_value)
+        .min(reward);
+    let assets_to_fee = vault.performance_fee_in_bps()?.apply(gain)?.fee;
+
+    if assets_to_fee > 0 {
+        vault.del_token(assets_to_fee)?;
+        vault.add_fee(assets_to_fee)?;
+    }
+    vault.raise_high_water_mark()?;
+
+    Ok(assets_to_fee)
+}
+
+/// Pays out all accrued fees, depositor assets are left untouched
 pub fn vault_collect_fee(vault: &mut Vault) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::CollectFee)?;
     let fee_amount = vault.fee_amount();
 
     vault.clear_fee_amount();
@@ -0,0 +1,72 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    state::{ExitFeeTier, Vault, VaultStatus, EXIT_FEE_TIERS, VAULT_DISCRIMINATOR, VAULT_VERSION},
+    utils::{guards::require, math::FeeBps},
+    VaultResult,
+};
+
+pub struct VaultInitParams {
+    pub admin: Pubkey,
+    pub slash_admin: Pubkey,
+    pub shares_mint: Pubkey,
+    pub assets_mint: Pubkey,
+    pub fee_bps: u64,
+    pub fee_token_account: Pubkey,
+    pub vault_assets_account: Pubkey,
+    pub vault_assets_account_bump: u8,
+    pub shares_mint_authority_bump: u8,
+    pub decimals_offset: u8,
+    pub management_fee_bps: u64,
+    pub performance_fee_bps: u64,
+    pub exit_fee_tiers: [ExitFeeTier; EXIT_FEE_TIERS],
+    pub exit_fee_holding_period: i64,
+    pub exit_fee_held_bps: u64,
+    pub now: i64,
+}
+
+pub fn vault_initialize(vault: &mut Vault, params: VaultInitParams) -> VaultResult<()> {
+    require!(
+        !vault.is_initialized(),
+        crate::errors::VaultError::AlreadyInitialized
+    );
+
+    // -- fee must be a valid fraction
+    let _: FeeBps = params.fee_bps.try_into()?;
+    let _: FeeBps = params.management_fee_bps.try_into()?;
+    let _: FeeBps = params.performance_fee_bps.try_into()?;
+
+    *vault = Vault {
+        discriminator: VAULT_DISCRIMINATOR,
+        version: VAULT_VERSION,
+        admin: params.admin,
+        slash_admin: params.slash_admin,
+        shares_mint: params.shares_mint,
+        assets_mint: params.assets_mint,
+        shares: 0u64.into(),
+        assets: 0u64.into(),
+        fee_bps: params.fee_bps.into(),
+        fee_amount: 0u64.into(),
+        fee_token_account: params.fee_token_account,
+        vault_assets_account: params.vault_assets_account,
+        vault_assets_account_bump: params.vault_assets_account_bump,
+        shares_mint_authority_bump: params.shares_mint_authority_bump,
+        decimals_offset: params.decimals_offset,
+        management_fee_bps: params.management_fee_bps.into(),
+        last_fee_accrual_ts: params.now.into(),
+        performance_fee_bps: params.performance_fee_bps.into(),
+        high_water_mark_assets: 0u64.into(),
+        high_water_mark_shares: 0u64.into(),
+        exit_fee_tiers: params.exit_fee_tiers,
+        exit_fee_holding_period: params.exit_fee_holding_period.into(),
+        exit_fee_held_bps: params.exit_fee_held_bps.into(),
+        pending_admin: Pubkey::default(),
+        pending_slash_admin: Pubkey::default(),
+        roles: Default::default(),
+        status: VaultStatus::Active as u8,
+    };
+    vault.set_high_water_mark();
+    vault.validate_exit_fee()?;
+
+    vault.validate()
+}
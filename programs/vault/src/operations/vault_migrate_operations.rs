@@ -0,0 +1,261 @@
+use std::mem::size_of;
+
+use crate::{
+    state::{vault_data_version, vault_layout_size, Vault, VAULT_VERSION},
+    VaultError, VaultResult,
+};
+
+/// Migration steps: `VAULT_MIGRATIONS[n - 1]` upgrades layout `n` to `n + 1`
+///
+/// A step runs after the fields added by the new layout are zero-initialized
+/// and only needs to set the ones whose default is not zero.
+const VAULT_MIGRATIONS: &[fn(&mut Vault)] = &[
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+];
+
+/// Layout 2 adds `decimals_offset`, zero keeps shares and assets at the same scale
+fn migrate_v1_to_v2(_vault: &mut Vault) {}
+
+/// Layout 3 adds the management fee, disabled until configured
+fn migrate_v2_to_v3(_vault: &mut Vault) {}
+
+/// Layout 4 adds the performance fee, the high-water mark starts at the current price
+fn migrate_v3_to_v4(vault: &mut Vault) {
+    vault.set_high_water_mark();
+}
+
+/// Layout 5 adds the exit fee, with no tiers no fee is charged
+fn migrate_v4_to_v5(_vault: &mut Vault) {}
+
+/// Layout 6 adds the pending authorities, no rotation is pending
+fn migrate_v5_to_v6(_vault: &mut Vault) {}
+
+/// Layout 7 adds the roles table, empty since the admin keeps collecting fees
+/// and updating rewards through [`crate::state::ADMIN_DEFAULT_ROLES`]
+fn migrate_v6_to_v7(_vault: &mut Vault) {}
+
+/// Layout 8 adds the status, zero is [`crate::state::VaultStatus::Active`]
+fn migrate_v7_to_v8(_vault: &mut Vault) {}
+
+const _: () = assert!(VAULT_MIGRATIONS.len() + 1 == VAULT_VERSION as usize);
+
+fn vault_upgrade(vault: &mut Vault, from_version: u8) {
+    for step in &VAULT_MIGRATIONS[(from_version - 1) as usize..] {
+        step(vault);
+    }
+    vault.version = VAULT_VERSION;
+}
+
+/// Upgrades vault data in place to the current layout
+///
+/// `data` must already be large enough to hold the current layout.
+/// Returns the layout version the data was stored in.
+pub fn vault_migrate(data: &mut [u8]) -> VaultResult<u8> {
+    let from_version = vault_data_version(data)?;
+    let old_size = vault_layout_size(from_version).ok_or(VaultError::UnsupportedVersion)?;
+    let data = data
+        .get_mut(..size_of::<Vault>())
+        .ok_or(VaultError::InvalidLayout)?;
+
+    data[old_size..].fill(0);
+    vault_upgrade(bytemuck::from_bytes_mut::<Vault>(data), from_version);
+    Ok(from_version)
+}
+
+/// Reads a vault stored in any supported layout version, upgraded to the current layout
+pub fn vault_load_any_version(data: &[u8]) -> VaultResult<Vault> {
+    let from_version = vault_data_version(data)?;
+    let size = vault_layout_size(from_version).ok_or(VaultError::UnsupportedVersion)?;
+    let data = data.get(..size).ok_or(VaultError::InvalidLayout)?;
+
+    let mut vault = Vault::default();
+    bytemuck::bytes_of_mut(&mut vault)[..size].copy_from_slice(data);
+    vault_upgrade(&mut vault, from_version);
+    Ok(vault)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{
+        AuthorityRole, ExitFeeTier, Role, RoleEntry, VaultStatus, EXIT_FEE_TIERS, ROLE_ENTRIES,
+        VAULT_DISCRIMINATOR,
+    };
+    use solana_program::pubkey::Pubkey;
+
+    fn new_test_vault() -> Vault {
+        Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            admin: Pubkey::new_unique(),
+            slash_admin: Pubkey::new_unique(),
+            shares_mint: Pubkey::new_unique(),
+            assets_mint: Pubkey::new_unique(),
+            shares: 100u64.into(),
+            assets: 200u64.into(),
+            fee_bps: 500u64.into(),
+            fee_amount: 7u64.into(),
+            fee_token_account: Pubkey::new_unique(),
+            vault_assets_account: Pubkey::new_unique(),
+            vault_assets_account_bump: 254,
+            shares_mint_authority_bump: 253,
+            decimals_offset: 3,
+            management_fee_bps: 200u64.into(),
+            last_fee_accrual_ts: 1_700_000_000i64.into(),
+            performance_fee_bps: 1_000u64.into(),
+            high_water_mark_assets: 300u64.into(),
+            high_water_mark_shares: 1_100u64.into(),
+            exit_fee_tiers: [ExitFeeTier {
+                min_assets: 1_000u64.into(),
+                fee_bps: 50u64.into(),
+            }; EXIT_FEE_TIERS],
+            exit_fee_holding_period: 86_400i64.into(),
+            exit_fee_held_bps: 10u64.into(),
+            pending_admin: Pubkey::new_unique(),
+            pending_slash_admin: Pubkey::new_unique(),
+            roles: [RoleEntry {
+                authority: Pubkey::new_unique(),
+                roles: Role::Slasher.mask(),
+            }; ROLE_ENTRIES],
+            status: VaultStatus::WindDown as u8,
+        }
+    }
+
+    /// Account data of `vault` as it was stored in layout `version`
+    fn vault_data_at_version(vault: &Vault, version: u8) -> Vec<u8> {
+        let size = vault_layout_size(version).unwrap();
+        let mut data = bytemuck::bytes_of(vault)[..size].to_vec();
+        data[8] = version;
+        data
+    }
+
+    /// Migrates `vault` stored at layout `version` both in place and in memory
+    fn migrate_from(vault: &Vault, version: u8) -> Vault {
+        let old_data = vault_data_at_version(vault, version);
+        let loaded = vault_load_any_version(&old_data).unwrap();
+
+        let mut data = old_data.clone();
+        data.resize(size_of::<Vault>(), 0);
+        assert_eq!(vault_migrate(&mut data).unwrap(), version);
+        assert_eq!(bytemuck::bytes_of(&loaded), &data[..]);
+
+        loaded
+    }
+
+    #[test]
+    fn test_migrate_current_version_is_noop() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, VAULT_VERSION);
+        assert_eq!(bytemuck::bytes_of(&migrated), bytemuck::bytes_of(&vault));
+    }
+
+    #[test]
+    fn test_migrate_from_v1() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 1);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(migrated.decimals_offset, 0);
+        assert_eq!(u64::from(migrated.management_fee_bps), 0);
+        assert_eq!(
+            bytemuck::bytes_of(&migrated)[9..vault_layout_size(1).unwrap()],
+            bytemuck::bytes_of(&vault)[9..vault_layout_size(1).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_migrate_from_v2() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 2);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(migrated.decimals_offset, vault.decimals_offset);
+        assert_eq!(u64::from(migrated.management_fee_bps), 0);
+        assert_eq!(migrated.last_fee_accrual_ts(), 0);
+    }
+
+    #[test]
+    fn test_migrate_from_v3() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 3);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(u64::from(migrated.performance_fee_bps), 0);
+        // -- 200 assets + 1 virtual asset for 100 shares + 1_000 virtual shares
+        assert_eq!(u64::from(migrated.high_water_mark_assets), 201);
+        assert_eq!(u64::from(migrated.high_water_mark_shares), 1_100);
+    }
+
+    #[test]
+    fn test_migrate_from_v4() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 4);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(
+            migrated.exit_fee_tiers,
+            [ExitFeeTier::default(); EXIT_FEE_TIERS]
+        );
+        assert_eq!(migrated.exit_fee_in_bps(u64::MAX, None).unwrap().bps(), 0);
+    }
+
+    #[test]
+    fn test_migrate_from_v5() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 5);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(migrated.pending_authority(AuthorityRole::Admin), None);
+        assert_eq!(migrated.pending_authority(AuthorityRole::SlashAdmin), None);
+    }
+
+    #[test]
+    fn test_migrate_from_v6() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 6);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert!(migrated.has_role(&vault.admin, Role::FeeManager));
+        assert!(migrated.has_role(&vault.admin, Role::RewardUpdater));
+        assert_eq!(migrated.roles, [RoleEntry::default(); ROLE_ENTRIES]);
+        assert!(!migrated.has_role(&vault.roles[1].authority, Role::Slasher));
+    }
+
+    #[test]
+    fn test_migrate_from_v7() {
+        let vault = new_test_vault();
+        let migrated = migrate_from(&vault, 7);
+
+        assert_eq!(migrated.version, VAULT_VERSION);
+        assert_eq!(migrated.status().unwrap(), VaultStatus::Active);
+    }
+
+    #[test]
+    fn test_migrate_rejects_unknown_version() {
+        let vault = new_test_vault();
+        let mut data = bytemuck::bytes_of(&vault).to_vec();
+
+        data[8] = 0;
+        assert!(vault_load_any_version(&data).is_err());
+        assert!(vault_migrate(&mut data).is_err());
+
+        data[8] = VAULT_VERSION + 1;
+        assert!(vault_load_any_version(&data).is_err());
+        assert!(vault_migrate(&mut data).is_err());
+    }
+
+    #[test]
+    fn test_migrate_rejects_truncated_data() {
+        let vault = new_test_vault();
+        let data = vault_data_at_version(&vault, VAULT_VERSION);
+
+        assert!(vault_load_any_version(&data[..data.len() - 1]).is_err());
+        assert!(vault_migrate(&mut data[..data.len() - 1].to_vec()).is_err());
+    }
+}
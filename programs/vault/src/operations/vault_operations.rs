@@ -1,8 +1,18 @@
-use crate::{state::Vault, VaultError, VaultResult};
+use crate::{
+    state::{UserHolding, Vault, VaultAction},
+    VaultError, VaultResult,
+};
+
+use super::vault_charge_performance_fee;
 
 /// Vault operations
 
 #[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "scenario",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
 pub struct VaultEffect {
     pub shares_to_burn: u64,
     pub shares_to_user: u64,
@@ -14,6 +24,7 @@ pub struct VaultEffect {
 pub fn vault_deposit_assets(vault: &mut Vault, tkn_amt: u64) -> VaultResult<VaultEffect> {
     //let fee = vault.fee_in_bps()?;
     //require!(fee.is_zero(), crate::errors::VaultError::GuardFail);
+    vault.require_allows(VaultAction::Deposit)?;
 
     let shares_to_user = vault.convert_assets_to_shares(tkn_amt)?;
 
@@ -27,31 +38,106 @@ pub fn vault_deposit_assets(vault: &mut Vault, tkn_amt: u64) -> VaultResult<Vaul
     })
 }
 
-pub fn vault_redeem_shares(vault: &mut Vault, shares_amt: u64) -> VaultResult<VaultEffect> {
-    let assets_to_user = vault.convert_shares_to_assets(shares_amt)?;
+/// Redeem `shares_amt` shares. The exit fee is taken from the redeemed assets.
+pub fn vault_redeem_shares(
+    vault: &mut Vault,
+    shares_amt: u64,
+    holding: Option<UserHolding>,
+) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Redeem)?;
+    let assets = vault.convert_shares_to_assets(shares_amt)?;
+    let held_for = holding
+        .filter(|holding| holding.shares >= shares_amt)
+        .map(|holding| holding.held_for);
+    let gross = vault.exit_fee_in_bps(assets, held_for)?.apply(assets)?;
+
     vault.burn_shares(shares_amt)?;
-    vault.del_token(assets_to_user)?;
+    vault.del_token(assets)?;
+    vault.add_fee(gross.fee)?;
 
     Ok(VaultEffect {
-        assets_to_user,
+        assets_to_user: gross.net_amount,
+        assets_to_fee: gross.fee,
         shares_to_burn: shares_amt,
         ..Default::default()
     })
 }
 
+/// Mint exactly `shares_amt` shares. The user pays assets rounded up.
+pub fn vault_mint_shares_exact(vault: &mut Vault, shares_amt: u64) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Deposit)?;
+    let assets_to_vault = vault.convert_shares_to_assets_ceil(shares_amt)?;
+
+    vault.mint_shares(shares_amt)?;
+    vault.add_token(assets_to_vault)?;
+
+    Ok(VaultEffect {
+        shares_to_user: shares_amt,
+        assets_to_vault,
+        ..Default::default()
+    })
+}
+
+/// Withdraw exactly `tkn_amt` assets. The user burns shares rounded up,
+/// for the assets and the exit fee on top of them.
+pub fn vault_withdraw_assets(
+    vault: &mut Vault,
+    tkn_amt: u64,
+    holding: Option<UserHolding>,
+) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Redeem)?;
+    let quote = |held_for| -> VaultResult<_> {
+        let gross = vault
+            .exit_fee_in_bps(tkn_amt, held_for)?
+            .gross_up(tkn_amt)?;
+        let shares_to_burn = vault.convert_assets_to_shares_ceil(gross.total()?)?;
+        Ok((gross, shares_to_burn))
+    };
+
+    // -- the reduced rate only covers shares held long enough
+    let (gross, shares_to_burn) = match holding {
+        Some(holding) => match quote(Some(holding.held_for))? {
+            (gross, shares_to_burn) if shares_to_burn <= holding.shares => (gross, shares_to_burn),
+            _ => quote(None)?,
+        },
+        None => quote(None)?,
+    };
+
+    vault.burn_shares(shares_to_burn)?;
+    vault.del_token(gross.total()?)?;
+    vault.add_fee(gross.fee)?;
+
+    Ok(VaultEffect {
+        assets_to_user: tkn_amt,
+        assets_to_fee: gross.fee,
+        shares_to_burn,
+        ..Default::default()
+    })
+}
+
 pub fn vault_update_reward(vault: &mut Vault, new_amt: u64) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::UpdateReward)?;
+
+    // -- accrued fees are held in the same token account
     let reward = new_amt
-        .checked_sub(vault.num_assets())
+        .checked_sub(vault.gross_assets())
         .ok_or(VaultError::MathOverflow)?;
 
-    if reward > 0 {
-        vault.add_token(reward)?;
+    if reward == 0 {
+        return Ok(VaultEffect::default());
     }
 
-    Ok(VaultEffect::default())
+    vault.add_token(reward)?;
+    let assets_to_fee = vault_charge_performance_fee(vault, reward)?;
+
+    Ok(VaultEffect {
+        assets_to_fee,
+        ..Default::default()
+    })
 }
 
 pub fn vault_process_slash(vault: &mut Vault, slash_amt: u64) -> VaultResult<VaultEffect> {
+    vault.require_allows(VaultAction::Slash)?;
     vault.del_token(slash_amt)?;
     Ok(VaultEffect {
         assets_to_user: slash_amt,
@@ -62,10 +148,23 @@ pub fn vault_process_slash(vault: &mut Vault, slash_amt: u64) -> VaultResult<Vau
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::operations::{
+        vault_accept_authority, vault_accrue_management_fee, vault_apply_param_change,
+        vault_cancel_authority, vault_collect_fee, vault_deposit_assets_with_fee,
+        vault_deposit_assets_with_fee_exact, vault_propose_authority, vault_set_roles,
+        vault_set_status, Slippage,
+    };
+    use crate::state::{
+        AuthorityRole, ExitFeeTier, ParamChange, Role, VaultStatus, FEE_BPS_CAP, ROLE_ENTRIES,
+        VAULT_DISCRIMINATOR, VAULT_VERSION,
+    };
+    use crate::utils::math::SECONDS_PER_YEAR;
     use solana_program::pubkey::Pubkey;
 
     fn new_test_vault() -> Vault {
         Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
             admin: Pubkey::default(),
             slash_admin: Pubkey::default(),
             shares_mint: Pubkey::default(),
@@ -74,6 +173,20 @@ mod tests {
             assets: 100u64.into(),
             vault_assets_account: Pubkey::default(),
             vault_assets_account_bump: 0,
+            shares_mint_authority_bump: 0,
+            decimals_offset: 0,
+            management_fee_bps: 0u64.into(),
+            last_fee_accrual_ts: 0i64.into(),
+            performance_fee_bps: 0u64.into(),
+            high_water_mark_assets: 101u64.into(),
+            high_water_mark_shares: 101u64.into(),
+            exit_fee_tiers: Default::default(),
+            exit_fee_holding_period: 0i64.into(),
+            exit_fee_held_bps: 0u64.into(),
+            pending_admin: Pubkey::default(),
+            pending_slash_admin: Pubkey::default(),
+            roles: Default::default(),
+            status: VaultStatus::Active as u8,
             fee_amount: 0u64.into(),
             fee_bps: 500u64.into(), // 5%
             fee_token_account: Pubkey::default(),
@@ -93,13 +206,398 @@ mod tests {
     #[test]
     fn test_vault_redeem_shares() {
         let mut vault = new_test_vault();
-        let effect = vault_redeem_shares(&mut vault, 40).unwrap();
+        let effect = vault_redeem_shares(&mut vault, 40, None).unwrap();
         assert_eq!(effect.assets_to_user, 40);
         assert_eq!(effect.shares_to_burn, 40);
         assert_eq!(vault.num_assets(), 60);
         assert_eq!(vault.num_shares(), 60);
     }
 
+    #[test]
+    fn test_vault_mint_shares_exact() {
+        let mut vault = new_test_vault();
+        vault.assets = 150u64.into();
+        let effect = vault_mint_shares_exact(&mut vault, 3).unwrap();
+        // -- 3 * 150 / 100 = 4.5, rounded up in favour of the vault
+        assert_eq!(effect.shares_to_user, 3);
+        assert_eq!(effect.assets_to_vault, 5);
+        assert_eq!(vault.num_assets(), 155);
+        assert_eq!(vault.num_shares(), 103);
+    }
+
+    #[test]
+    fn test_vault_withdraw_assets() {
+        let mut vault = new_test_vault();
+        vault.assets = 150u64.into();
+        let effect = vault_withdraw_assets(&mut vault, 4, None).unwrap();
+        // -- 4 * 100 / 150 = 2.67, rounded up in favour of the vault
+        assert_eq!(effect.assets_to_user, 4);
+        assert_eq!(effect.shares_to_burn, 3);
+        assert_eq!(vault.num_assets(), 146);
+        assert_eq!(vault.num_shares(), 97);
+    }
+
+    #[test]
+    fn test_vault_previews_match_operations() {
+        let mut vault = new_test_vault();
+        vault.assets = 150u64.into();
+
+        let preview = vault.preview_deposit(41).unwrap();
+        let effect = vault_deposit_assets_with_fee(&mut vault.clone(), 41).unwrap();
+        assert_eq!(preview, effect.shares_to_user);
+
+        let preview = vault.preview_mint(3).unwrap();
+        let effect = vault_mint_shares_exact(&mut vault.clone(), 3).unwrap();
+        assert_eq!(preview, effect.assets_to_vault);
+
+        let preview = vault.preview_withdraw(4).unwrap();
+        let effect = vault_withdraw_assets(&mut vault.clone(), 4, None).unwrap();
+        assert_eq!(preview, effect.shares_to_burn);
+
+        let preview = vault.preview_redeem(7).unwrap();
+        let effect = vault_redeem_shares(&mut vault.clone(), 7, None).unwrap();
+        assert_eq!(preview, effect.assets_to_user);
+    }
+
+    #[test]
+    fn test_vault_max_limits() {
+        let mut vault = new_test_vault();
+        vault.fee_amount = 10u64.into();
+        assert_eq!(vault.max_deposit(), u64::MAX - 110);
+        assert_eq!(vault.max_redeem(30), 30);
+        assert_eq!(vault.max_redeem(300), 100);
+    }
+
+    #[test]
+    fn test_vault_decimals_offset_limits_inflation() {
+        let mut vault = new_test_vault();
+        vault.shares = 0u64.into();
+        vault.assets = 0u64.into();
+        vault.decimals_offset = 3;
+
+        // -- Bob deposits one atom, then donates to inflate the share price
+        let bob_shares = vault_deposit_assets(&mut vault, 1).unwrap().shares_to_user;
+        assert_eq!(bob_shares, 1_000);
+        vault_update_reward(&mut vault, 10_001).unwrap();
+
+        // -- Alice still gets shares, and loses only a few atoms to rounding
+        let alice_shares = vault_deposit_assets(&mut vault, 5_000)
+            .unwrap()
+            .shares_to_user;
+        assert_eq!(alice_shares, 999);
+        let bob_assets = vault_redeem_shares(&mut vault, bob_shares, None)
+            .unwrap()
+            .assets_to_user;
+        let alice_assets = vault_redeem_shares(&mut vault, alice_shares, None)
+            .unwrap()
+            .assets_to_user;
+        assert!(bob_assets < 10_001);
+        assert!(alice_assets >= 5_000 - 10);
+        assert!(vault.is_solvent());
+    }
+
+    #[test]
+    fn test_vault_fee_accrues_until_collected() {
+        let mut vault = new_test_vault();
+        let effect = vault_deposit_assets_with_fee(&mut vault, 100).unwrap();
+        assert_eq!(effect.assets_to_vault, 95);
+        assert_eq!(effect.assets_to_fee, 5);
+        assert_eq!(vault.fee_amount(), 5);
+        assert_eq!(vault.num_assets(), 195);
+        assert_eq!(vault.gross_assets(), 200);
+
+        // -- the accrued fee is not a reward for depositors
+        vault_update_reward(&mut vault, 200).unwrap();
+        assert_eq!(vault.num_assets(), 195);
+
+        let effect = vault_collect_fee(&mut vault).unwrap();
+        assert_eq!(effect.assets_to_user, 5);
+        assert_eq!(vault.fee_amount(), 0);
+        assert_eq!(vault.num_assets(), 195);
+        assert_eq!(vault.num_shares(), 195);
+    }
+
+    #[test]
+    fn test_vault_deposit_assets_with_fee_exact() {
+        let mut vault = new_test_vault();
+        vault.assets = 150u64.into();
+        let effect = vault_deposit_assets_with_fee_exact(&mut vault, 100).unwrap();
+        // -- 95 net assets buy 63 shares, worth 94 assets, charged 4 in fees
+        assert_eq!(effect.shares_to_user, 63);
+        assert_eq!(effect.assets_to_vault, 94);
+        assert_eq!(effect.assets_to_fee, 4);
+        assert_eq!(vault.num_shares(), 163);
+        assert_eq!(vault.num_assets(), 244);
+        assert_eq!(vault.fee_amount(), 4);
+    }
+
+    #[test]
+    fn test_vault_accrue_management_fee() {
+        let mut vault = new_test_vault();
+        vault.shares = 500u64.into();
+        vault.assets = 1_000u64.into();
+        vault.management_fee_bps = 200u64.into(); // 2% per year
+
+        // -- half a year accrues half the annual rate
+        let half_year = SECONDS_PER_YEAR as i64 / 2;
+        let effect = vault_accrue_management_fee(&mut vault, half_year).unwrap();
+        assert_eq!(effect.assets_to_fee, 10);
+        assert_eq!(vault.num_assets(), 990);
+        assert_eq!(vault.fee_amount(), 10);
+        assert_eq!(vault.last_fee_accrual_ts(), half_year);
+
+        // -- no time elapsed, no fee
+        let effect = vault_accrue_management_fee(&mut vault, half_year).unwrap();
+        assert_eq!(effect.assets_to_fee, 0);
+
+        // -- the fee is capped to keep every share backed
+        vault.management_fee_bps = 10_000u64.into();
+        vault_accrue_management_fee(&mut vault, i64::MAX).unwrap();
+        assert_eq!(vault.num_assets(), 500);
+        assert!(vault.is_solvent());
+    }
+
+    #[test]
+    fn test_vault_performance_fee_above_high_water_mark() {
+        let mut vault = new_test_vault();
+        vault.performance_fee_bps = 1_000u64.into(); // 10%
+
+        // -- the whole reward is above the mark
+        let effect = vault_update_reward(&mut vault, 150).unwrap();
+        assert_eq!(effect.assets_to_fee, 5);
+        assert_eq!(vault.num_assets(), 145);
+        assert_eq!(vault.fee_amount(), 5);
+        assert_eq!(u64::from(vault.high_water_mark_assets), 146);
+        assert_eq!(u64::from(vault.high_water_mark_shares), 101);
+
+        // -- the mark survives a slash, recovering up to it is free
+        vault_process_slash(&mut vault, 45).unwrap();
+        let effect = vault_update_reward(&mut vault, 125).unwrap();
+        assert_eq!(effect.assets_to_fee, 0);
+        assert_eq!(vault.num_assets(), 120);
+        assert_eq!(u64::from(vault.high_water_mark_assets), 146);
+    }
+
+    #[test]
+    fn test_vault_exit_fee_tiers() {
+        let mut vault = new_test_vault();
+        vault.shares = 10_000u64.into();
+        vault.assets = 10_000u64.into();
+        vault.exit_fee_tiers[0] = ExitFeeTier {
+            min_assets: 0u64.into(),
+            fee_bps: 100u64.into(), // 1%
+        };
+        for tier in &mut vault.exit_fee_tiers[1..] {
+            *tier = ExitFeeTier {
+                min_assets: 5_000u64.into(),
+                fee_bps: 200u64.into(), // 2%
+            };
+        }
+        vault.exit_fee_holding_period = (7 * 24 * 3600i64).into();
+        vault.exit_fee_held_bps = 50u64.into(); // 0.5%
+
+        let effect = vault_redeem_shares(&mut vault.clone(), 4_000, None).unwrap();
+        assert_eq!(effect.assets_to_user, 3_960);
+        assert_eq!(effect.assets_to_fee, 40);
+
+        let effect = vault_withdraw_assets(&mut vault.clone(), 3_960, None).unwrap();
+        assert_eq!(effect.assets_to_fee, 40);
+        assert_eq!(effect.shares_to_burn, 4_000);
+
+        // -- the reduced rate only applies to shares held long enough
+        let held = UserHolding {
+            held_for: 10 * 24 * 3600,
+            shares: 6_000,
+        };
+        let effect = vault_redeem_shares(&mut vault.clone(), 6_000, Some(held)).unwrap();
+        assert_eq!(effect.assets_to_fee, 30);
+        let held = UserHolding {
+            shares: 5_000,
+            ..held
+        };
+        let effect = vault_redeem_shares(&mut vault, 6_000, Some(held)).unwrap();
+        assert_eq!(effect.assets_to_fee, 120);
+        assert_eq!(vault.num_assets(), 4_000);
+        assert_eq!(vault.fee_amount(), 120);
+    }
+
+    #[test]
+    fn test_vault_single_exit_fee_tier() {
+        let mut vault = new_test_vault();
+        vault.shares = 10_000u64.into();
+        vault.assets = 10_000u64.into();
+        vault.exit_fee_tiers[0] = ExitFeeTier {
+            min_assets: 1_000u64.into(),
+            fee_bps: 100u64.into(), // 1%
+        };
+        vault.validate_exit_fee().unwrap();
+        assert_eq!(vault.active_exit_fee_tiers().len(), 1);
+
+        // -- the unused zero tiers do not reset the rate
+        let effect = vault_redeem_shares(&mut vault.clone(), 999, None).unwrap();
+        assert_eq!(effect.assets_to_fee, 0);
+        let effect = vault_redeem_shares(&mut vault.clone(), 2_000, None).unwrap();
+        assert_eq!(effect.assets_to_fee, 20);
+
+        // -- a tier after an unused one is rejected
+        vault.exit_fee_tiers[2] = ExitFeeTier {
+            min_assets: 5_000u64.into(),
+            fee_bps: 200u64.into(),
+        };
+        assert!(vault.validate_exit_fee().is_err());
+    }
+
+    #[test]
+    fn test_vault_param_change_fee_cap() {
+        let mut vault = new_test_vault();
+        vault_apply_param_change(&mut vault, ParamChange::FeeBps(FEE_BPS_CAP)).unwrap();
+        assert_eq!(u64::from(vault.fee_bps), FEE_BPS_CAP);
+        assert!(
+            vault_apply_param_change(&mut vault, ParamChange::FeeBps(FEE_BPS_CAP + 1)).is_err()
+        );
+
+        // -- a fee above the cap can still be lowered
+        vault.fee_bps = 2_000u64.into();
+        vault_apply_param_change(&mut vault, ParamChange::FeeBps(1_500)).unwrap();
+        assert_eq!(u64::from(vault.fee_bps), 1_500);
+
+        assert!(
+            vault_apply_param_change(&mut vault, ParamChange::Admin(Pubkey::default())).is_err()
+        );
+    }
+
+    #[test]
+    fn test_vault_authority_rotation() {
+        let mut vault = new_test_vault();
+        let new_admin = Pubkey::new_unique();
+        assert!(vault_accept_authority(&mut vault, AuthorityRole::Admin).is_err());
+
+        vault_propose_authority(&mut vault, AuthorityRole::Admin, new_admin).unwrap();
+        assert_eq!(vault.admin, Pubkey::default());
+        vault_cancel_authority(&mut vault, AuthorityRole::Admin).unwrap();
+        assert!(vault_accept_authority(&mut vault, AuthorityRole::Admin).is_err());
+
+        vault_propose_authority(&mut vault, AuthorityRole::Admin, new_admin).unwrap();
+        vault_accept_authority(&mut vault, AuthorityRole::Admin).unwrap();
+        assert_eq!(vault.admin, new_admin);
+        assert_eq!(vault.pending_authority(AuthorityRole::Admin), None);
+        assert_eq!(vault.slash_admin, Pubkey::default());
+    }
+
+    #[test]
+    fn test_vault_roles() {
+        let mut vault = new_test_vault();
+        let pauser = Pubkey::new_unique();
+        assert!(!vault.has_role(&pauser, Role::Pauser));
+
+        vault_set_roles(&mut vault, pauser, Role::Pauser.mask()).unwrap();
+        assert!(vault.has_role(&pauser, Role::Pauser));
+        assert!(!vault.has_role(&pauser, Role::FeeManager));
+        // -- admin can only be rotated, never granted
+        assert!(vault_set_roles(&mut vault, pauser, Role::Admin.mask()).is_err());
+
+        for _ in 1..ROLE_ENTRIES {
+            vault_set_roles(&mut vault, Pubkey::new_unique(), Role::Slasher.mask()).unwrap();
+        }
+        let slasher = Pubkey::new_unique();
+        assert!(vault_set_roles(&mut vault, slasher, Role::Slasher.mask()).is_err());
+
+        // -- revoking frees the entry
+        vault_set_roles(&mut vault, pauser, 0).unwrap();
+        assert!(!vault.has_role(&pauser, Role::Pauser));
+        vault_set_roles(&mut vault, slasher, Role::Slasher.mask()).unwrap();
+        assert!(vault.has_role(&slasher, Role::Slasher));
+    }
+
+    #[test]
+    fn test_vault_status_gates_operations() {
+        let mut vault = new_test_vault();
+
+        vault_set_status(&mut vault, VaultStatus::DepositsPaused).unwrap();
+        assert!(vault_deposit_assets(&mut vault, 10).is_err());
+        assert_eq!(vault.max_deposit(), 0);
+        vault_redeem_shares(&mut vault, 10, None).unwrap();
+
+        vault_set_status(&mut vault, VaultStatus::FullyPaused).unwrap();
+        assert!(vault_redeem_shares(&mut vault, 10, None).is_err());
+        assert!(vault_collect_fee(&mut vault).is_err());
+        assert_eq!(vault.max_redeem(10), 0);
+        // -- no management fee accrues while fully paused
+        vault.management_fee_bps = 1_000u64.into();
+        vault_accrue_management_fee(&mut vault, SECONDS_PER_YEAR as i64).unwrap();
+        assert_eq!(vault.num_assets(), 90);
+
+        vault_set_status(&mut vault, VaultStatus::WindDown).unwrap();
+        vault_redeem_shares(&mut vault, 10, None).unwrap();
+        vault_collect_fee(&mut vault).unwrap();
+        assert!(vault_update_reward(&mut vault, 100).is_err());
+        assert!(vault_process_slash(&mut vault, 1).is_err());
+        // -- wind-down is final
+        assert!(vault_set_status(&mut vault, VaultStatus::Active).is_err());
+        assert_eq!(vault.num_shares(), 80);
+    }
+
+    #[test]
+    fn test_vault_cancel_param_change_in_every_status() {
+        let mut vault = new_test_vault();
+
+        for status in [
+            VaultStatus::Active,
+            VaultStatus::DepositsPaused,
+            VaultStatus::FullyPaused,
+            VaultStatus::WindDown,
+        ] {
+            vault_set_status(&mut vault, status).unwrap();
+            vault
+                .require_allows(VaultAction::CancelParamChange)
+                .unwrap();
+        }
+        // -- while queueing and executing stay blocked
+        assert!(vault.require_allows(VaultAction::Configure).is_err());
+    }
+
+    #[test]
+    fn test_vault_slippage_bounds() {
+        let mut vault = new_test_vault();
+        vault.assets = 150u64.into();
+
+        let effect = vault_mint_shares_exact(&mut vault, 3).unwrap();
+        let max_assets_in = |max| Slippage {
+            max_assets_in: Some(max),
+            ..Default::default()
+        };
+        max_assets_in(5).check(&effect).unwrap();
+        assert!(max_assets_in(4).check(&effect).is_err());
+
+        let effect = vault_withdraw_assets(&mut vault, 4, None).unwrap();
+        let max_shares_in = |max| Slippage {
+            max_shares_in: Some(max),
+            ..Default::default()
+        };
+        max_shares_in(3).check(&effect).unwrap();
+        assert!(max_shares_in(2).check(&effect).is_err());
+
+        let effect = vault_redeem_shares(&mut vault, 40, None).unwrap();
+        let min_assets_out = |min| Slippage {
+            min_assets_out: Some(min),
+            ..Default::default()
+        };
+        min_assets_out(effect.assets_to_user)
+            .check(&effect)
+            .unwrap();
+        assert!(min_assets_out(effect.assets_to_user + 1)
+            .check(&effect)
+            .is_err());
+
+        let deadline = Slippage {
+            deadline_slot: Some(10),
+            ..Default::default()
+        };
+        deadline.check_deadline(10).unwrap();
+        assert!(deadline.check_deadline(11).is_err());
+        Slippage::default().check_deadline(u64::MAX).unwrap();
+    }
+
     #[test]
     fn test_vault_process_slash() {
         let mut vault = new_test_vault();
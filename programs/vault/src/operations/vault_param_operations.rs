@@ -0,0 +1,54 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    state::{AuthorityRole, ParamChange, Vault, VaultAction, FEE_BPS_CAP},
+    utils::{
+        guards::{require_le, require_ne},
+        math::FeeBps,
+    },
+    VaultResult,
+};
+
+use super::vault_cancel_authority;
+
+/// Checks that `change` is acceptable for `vault`, both when it is queued
+/// and when it is executed
+pub fn vault_check_param_change(vault: &Vault, change: &ParamChange) -> VaultResult<()> {
+    vault.require_allows(VaultAction::Configure)?;
+    match *change {
+        ParamChange::FeeBps(fee_bps) => {
+            let _: FeeBps = fee_bps.try_into()?;
+            // -- lowering a fee is always allowed, raising it is capped
+            if fee_bps > u64::from(vault.fee_bps) {
+                require_le!(
+                    fee_bps,
+                    FEE_BPS_CAP,
+                    crate::errors::VaultError::FeeCapExceeded
+                );
+            }
+        }
+        ParamChange::FeeTokenAccount(pk) | ParamChange::Admin(pk) | ParamChange::SlashAdmin(pk) => {
+            require_ne!(pk, Pubkey::default(), crate::errors::VaultError::GuardFail);
+        }
+    }
+    Ok(())
+}
+
+/// Applies a queued parameter change whose timelock expired
+pub fn vault_apply_param_change(vault: &mut Vault, change: ParamChange) -> VaultResult<()> {
+    vault_check_param_change(vault, &change)?;
+    match change {
+        ParamChange::FeeBps(fee_bps) => vault.fee_bps = fee_bps.into(),
+        ParamChange::FeeTokenAccount(pk) => vault.fee_token_account = pk,
+        // -- a rotation proposed by the replaced holder is dropped
+        ParamChange::Admin(pk) => {
+            vault.admin = pk;
+            vault_cancel_authority(vault, AuthorityRole::Admin)?;
+        }
+        ParamChange::SlashAdmin(pk) => {
+            vault.slash_admin = pk;
+            vault_cancel_authority(vault, AuthorityRole::SlashAdmin)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    state::{Role, RoleEntry, Vault, VaultAction},
+    utils::guards::{require, require_ne},
+    VaultResult,
+};
+
+/// Every role that can be granted through the roles table
+const GRANTABLE_ROLES: u8 = Role::Slasher.mask()
+    | Role::FeeManager.mask()
+    | Role::Pauser.mask()
+    | Role::RewardUpdater.mask();
+
+/// Sets the roles of `authority` in the roles table, zero revokes them all
+pub fn vault_set_roles(vault: &mut Vault, authority: Pubkey, roles: u8) -> VaultResult<()> {
+    vault.require_allows(VaultAction::Configure)?;
+    require_ne!(
+        authority,
+        Pubkey::default(),
+        crate::errors::VaultError::GuardFail
+    );
+    require!(
+        roles & !GRANTABLE_ROLES == 0,
+        crate::errors::VaultError::GuardFail
+    );
+
+    let free = Pubkey::default();
+    let entry = match vault
+        .roles
+        .iter()
+        .position(|entry| entry.authority == authority)
+    {
+        Some(idx) => &mut vault.roles[idx],
+        // -- revoking the roles of an unknown authority is a no-op
+        None if roles == 0 => return Ok(()),
+        None => vault
+            .roles
+            .iter_mut()
+            .find(|entry| entry.authority == free)
+            .ok_or(crate::errors::VaultError::RoleTableFull)?,
+    };
+
+    *entry = if roles == 0 {
+        RoleEntry::default()
+    } else {
+        RoleEntry { authority, roles }
+    };
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use crate::{
+    utils::guards::{require_ge, require_le},
+    VaultResult,
+};
+
+use super::VaultEffect;
+
+/// Optional bounds on the outcome of a deposit or a withdrawal, protecting
+/// users from a conversion rate moved in front of their transaction
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Slippage {
+    pub min_shares_out: Option<u64>,
+    pub min_assets_out: Option<u64>,
+    /// Bounds what the user pays, assets and deposit fee
+    pub max_assets_in: Option<u64>,
+    pub max_shares_in: Option<u64>,
+    /// Last slot at which the operation may execute
+    pub deadline_slot: Option<u64>,
+}
+
+impl Slippage {
+    pub fn check_deadline(&self, slot: u64) -> VaultResult<()> {
+        if let Some(deadline_slot) = self.deadline_slot {
+            require_le!(
+                slot,
+                deadline_slot,
+                crate::errors::VaultError::DeadlineExceeded
+            );
+        }
+        Ok(())
+    }
+
+    pub fn check(&self, effect: &VaultEffect) -> VaultResult<()> {
+        if let Some(min_shares_out) = self.min_shares_out {
+            require_ge!(
+                effect.shares_to_user,
+                min_shares_out,
+                crate::errors::VaultError::SlippageExceeded
+            );
+        }
+        if let Some(min_assets_out) = self.min_assets_out {
+            require_ge!(
+                effect.assets_to_user,
+                min_assets_out,
+                crate::errors::VaultError::SlippageExceeded
+            );
+        }
+        if let Some(max_assets_in) = self.max_assets_in {
+            require_le!(
+                effect.assets_to_vault.saturating_add(effect.assets_to_fee),
+                max_assets_in,
+                crate::errors::VaultError::SlippageExceeded
+            );
+        }
+        if let Some(max_shares_in) = self.max_shares_in {
+            require_le!(
+                effect.shares_to_burn,
+                max_shares_in,
+                crate::errors::VaultError::SlippageExceeded
+            );
+        }
+        Ok(())
+    }
+}
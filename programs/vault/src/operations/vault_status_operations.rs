@@ -0,0 +1,19 @@
+use crate::{
+    state::{Vault, VaultStatus},
+    utils::guards::require_ne,
+    VaultResult,
+};
+
+/// Moves the vault to `status`, a vault in wind-down never comes back
+///
+/// The management fee must be accrued first, so that a fully paused period
+/// is skipped on unpause.
+pub fn vault_set_status(vault: &mut Vault, status: VaultStatus) -> VaultResult<()> {
+    require_ne!(
+        vault.status()?,
+        VaultStatus::WindDown,
+        crate::errors::VaultError::VaultPaused
+    );
+    vault.status = status as u8;
+    Ok(())
+}
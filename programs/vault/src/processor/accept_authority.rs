@@ -0,0 +1,17 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::PendingAuthorityContext, operations::vault_accept_authority, state::AuthorityRole,
+};
+
+pub fn process_accept_authority(accounts: &[AccountInfo], role: AuthorityRole) -> ProgramResult {
+    let PendingAuthorityContext {
+        vault_info,
+        authority: _,
+    } = PendingAuthorityContext::load(accounts, role)?;
+
+    let mut vault = vault_info.get_mut()?;
+    vault_accept_authority(&mut vault, role)?;
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::AuthorityHolderContext, operations::vault_cancel_authority, state::AuthorityRole,
+};
+
+pub fn process_cancel_authority(accounts: &[AccountInfo], role: AuthorityRole) -> ProgramResult {
+    let AuthorityHolderContext {
+        vault_info,
+        authority: _,
+    } = AuthorityHolderContext::load(accounts, role)?;
+
+    let mut vault = vault_info.get_mut()?;
+    vault_cancel_authority(&mut vault, role)?;
+
+    Ok(())
+}
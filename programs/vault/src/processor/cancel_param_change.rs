@@ -0,0 +1,20 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::CancelParamChangeContext, processor::close_program_account, state::VaultAction,
+};
+
+pub fn process_cancel_param_change(accounts: &[AccountInfo]) -> ProgramResult {
+    let CancelParamChangeContext {
+        vault_info,
+        pending_change,
+        authority: _,
+        rent_receiver,
+    } = CancelParamChangeContext::load(accounts)?;
+
+    vault_info
+        .get()?
+        .require_allows(VaultAction::CancelParamChange)?;
+
+    close_program_account(pending_change.as_ref(), &rent_receiver)
+}
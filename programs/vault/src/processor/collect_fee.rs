@@ -1,25 +1,39 @@
-pub fn process_collect_fee(accounts: &[AccountInfo]) -> ProgramResult {
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::CollectFeeContext,
+    operations::{vault_accrue_management_fee, vault_collect_fee},
+    vault_assets_account_seeds_with_bump,
+};
+
+use super::{clock_unix_timestamp, TokenProgram};
+
+pub fn process_collect_fee<T: TokenProgram>(token: &T, accounts: &[AccountInfo]) -> ProgramResult {
     let CollectFeeContext {
         vault_info,
         vault_assets_account,
         assets_mint,
         fee_collect_account,
         authority: _,
-        spl_token_program,
+        spl_token_program: _,
     } = CollectFeeContext::load(accounts)?;
 
+    let vault_assets_account_bump = vault_info.get()?.vault_assets_account_bump;
+
     let effect = {
         let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, clock_unix_timestamp()?)?;
         vault_collect_fee(&mut vault)?
     };
 
-    spl_transfer_assets_from_vault(
+    token.transfer(
         effect.assets_to_user,
-        &vault_assets_account,
-        &fee_collect_account,
-        &assets_mint,
-        spl_token_program.as_ref(),
+        vault_assets_account.as_ref(),
+        fee_collect_account.as_ref(),
+        assets_mint.as_ref(),
+        vault_assets_account.as_ref(),
+        vault_assets_account_seeds_with_bump!(vault_info.as_ref().key, vault_assets_account_bump),
     )?;
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,112 @@
+use std::mem::size_of;
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack,
+};
+
+use crate::{
+    instruction::CreateVaultArgs,
+    loaders::{CreateVaultContext, VaultInfo},
+    operations::{vault_initialize, VaultInitParams},
+    processor::{
+        clock_unix_timestamp, spl_initialize_mint, spl_initialize_token_account,
+        system_create_account, system_create_pda_account,
+    },
+    state::Vault,
+    vault_assets_account_seeds_with_bump, VaultError,
+};
+
+pub fn process_create_vault(accounts: &[AccountInfo], args: &CreateVaultArgs) -> ProgramResult {
+    let CreateVaultContext {
+        vault_info,
+        vault_assets_account,
+        assets_mint,
+        shares_mint,
+        payer,
+        spl_token_program,
+        system_program,
+        vault_assets_account_bump,
+        shares_mint_authority,
+        shares_mint_authority_bump,
+    } = CreateVaultContext::load(accounts)?;
+
+    let vault_pk = vault_info.as_ref().key;
+
+    // -- allocate the vault account
+    system_create_account(
+        payer.as_ref(),
+        vault_info.as_ref(),
+        size_of::<Vault>(),
+        &crate::ID,
+        system_program.as_ref(),
+        &[],
+    )?;
+
+    // -- allocate the PDA token account holding vault assets, owned by itself
+    system_create_pda_account(
+        payer.as_ref(),
+        &vault_assets_account,
+        spl_token::state::Account::LEN,
+        spl_token_program.as_ref().key,
+        system_program.as_ref(),
+        &[vault_assets_account_seeds_with_bump!(
+            vault_pk,
+            vault_assets_account_bump
+        )],
+    )?;
+    spl_initialize_token_account(
+        &vault_assets_account,
+        assets_mint.as_ref(),
+        vault_assets_account.key,
+        spl_token_program.as_ref(),
+    )?;
+
+    // -- allocate the shares mint, `decimals_offset` more decimals than the assets mint
+    let shares_decimals = assets_mint
+        .mint
+        .decimals
+        .checked_add(args.decimals_offset)
+        .ok_or(VaultError::MathOverflow)?;
+    system_create_account(
+        payer.as_ref(),
+        shares_mint.as_ref(),
+        spl_token::state::Mint::LEN,
+        spl_token_program.as_ref().key,
+        system_program.as_ref(),
+        &[],
+    )?;
+    spl_initialize_mint(
+        shares_mint.as_ref(),
+        &shares_mint_authority,
+        shares_decimals,
+        spl_token_program.as_ref(),
+    )?;
+
+    let vault_info = VaultInfo::unchecked(vault_info.as_ref());
+    let mut vault = vault_info.get_uninitialized_mut()?;
+    vault_initialize(
+        &mut vault,
+        VaultInitParams {
+            admin: args.admin,
+            slash_admin: args.slash_admin,
+            shares_mint: *shares_mint.as_ref().key,
+            assets_mint: *assets_mint.as_ref().key,
+            fee_bps: args.fee_bps.into(),
+            fee_token_account: args.fee_token_account,
+            vault_assets_account: *vault_assets_account.key,
+            vault_assets_account_bump,
+            shares_mint_authority_bump,
+            decimals_offset: args.decimals_offset,
+            management_fee_bps: args.management_fee_bps.into(),
+            performance_fee_bps: args.performance_fee_bps.into(),
+            exit_fee_tiers: args.exit_fee_tiers,
+            exit_fee_holding_period: args.exit_fee_holding_period.into(),
+            exit_fee_held_bps: args.exit_fee_held_bps.into(),
+            now: clock_unix_timestamp()?,
+        },
+    )
+    .map_err(|e| -> ProgramError { e.into() })?;
+
+    Ok(())
+}
@@ -4,11 +4,17 @@ use solana_program::{
 
 use crate::{
     loaders::DepositContext,
-    operations::vault_deposit_assets,
-    processor::{spl_mint_shares, spl_transfer_assets_from_user},
+    operations::{vault_accrue_management_fee, vault_deposit_assets, Slippage},
+    processor::{clock_slot, clock_unix_timestamp, TokenProgram},
+    shares_mint_authority_seeds_with_bump,
 };
 
-pub fn process_deposit(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+pub fn process_deposit<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    amount: u64,
+    slippage: Slippage,
+) -> ProgramResult {
     let DepositContext {
         vault_info,
         vault_assets_account,
@@ -17,35 +23,44 @@ pub fn process_deposit(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
         user_assets_account,
         authority,
         user_shares_account,
-        spl_token_program,
+        spl_token_program: _,
+        shares_mint_authority,
+        user_position,
     } = DepositContext::load(accounts)?;
 
-    /*require_ne!(
-        vault_assets_account.as_ref().key,
-        user_assets_account.key,
-        crate::errors::VaultError::SelfTransfer.into()
-    );*/
+    let shares_mint_authority_bump = vault_info.get()?.shares_mint_authority_bump;
 
+    slippage.check_deadline(clock_slot()?)?;
+
+    let now = clock_unix_timestamp()?;
     let effect = {
         let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, now)?;
         vault_deposit_assets(&mut vault, amount).map_err(|e| -> ProgramError { e.into() })?
     };
+    slippage.check(&effect)?;
+
+    if let Some(user_position) = &user_position {
+        user_position
+            .get_mut()?
+            .record_deposit(effect.shares_to_user, now)?;
+    }
 
-    spl_transfer_assets_from_user(
+    token.transfer(
         effect.assets_to_vault,
+        user_assets_account.as_ref(),
         vault_assets_account.as_ref(),
-        &user_assets_account,
-        &assets_mint,
+        assets_mint.as_ref(),
         authority.as_ref(),
-        spl_token_program.as_ref(),
+        &[],
     )?;
 
-    spl_mint_shares(
+    token.mint(
         effect.shares_to_user,
-        &user_shares_account,
-        &shares_mint,
-        authority.as_ref(),
-        spl_token_program.as_ref(),
+        user_shares_account.as_ref(),
+        shares_mint.as_ref(),
+        shares_mint_authority.as_ref(),
+        shares_mint_authority_seeds_with_bump!(vault_info.as_ref().key, shares_mint_authority_bump),
     )?;
 
     Ok(())
@@ -1,7 +1,7 @@
 use crate::{
-    operations::vault_deposit_assets_with_fee,
-    processor::{spl_mint_shares, spl_transfer_assets_from_user},
-    utils::guards::require_ne,
+    operations::{vault_accrue_management_fee, vault_deposit_assets_with_fee, Slippage},
+    processor::{clock_slot, clock_unix_timestamp, TokenProgram},
+    shares_mint_authority_seeds_with_bump,
 };
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -9,57 +9,64 @@ use solana_program::{
 
 use crate::loaders::DepositWithFeeContext;
 
-pub fn process_deposit_with_fee(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+pub fn process_deposit_with_fee<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    amount: u64,
+    slippage: Slippage,
+) -> ProgramResult {
     let DepositWithFeeContext {
         vault_info,
         vault_assets_account,
-        vault_fee_account,
         assets_mint,
         shares_mint,
         user_assets_account,
         authority,
         user_shares_account,
-        spl_token_program,
+        spl_token_program: _,
+        shares_mint_authority,
+        user_position,
     } = DepositWithFeeContext::load(accounts)?;
 
-    require_ne!(
-        vault_assets_account.key,
-        user_assets_account.key,
-        crate::errors::VaultError::SelfTransfer.into()
-    );
+    let shares_mint_authority_bump = vault_info.get()?.shares_mint_authority_bump;
 
+    slippage.check_deadline(clock_slot()?)?;
+
+    let now = clock_unix_timestamp()?;
     let effect = {
         let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, now)?;
         vault_deposit_assets_with_fee(&mut vault, amount)
             .map_err(|e| -> ProgramError { e.into() })?
     };
+    slippage.check(&effect)?;
 
-    // -- transfer assets into vault
-    spl_transfer_assets_from_user(
-        effect.assets_to_vault,
-        &vault_assets_account,
-        &user_assets_account,
-        &assets_mint,
-        authority.as_ref(),
-        spl_token_program.as_ref(),
-    )?;
+    if let Some(user_position) = &user_position {
+        user_position
+            .get_mut()?
+            .record_deposit(effect.shares_to_user, now)?;
+    }
 
-    // -- transfer fee from user to vault
-    spl_transfer_assets_from_user(
-        effect.assets_to_fee,
-        &vault_fee_account,
-        &user_assets_account,
-        &assets_mint,
+    // -- transfer assets and fee into vault, the fee accrues until collected
+    let assets_with_fee = effect
+        .assets_to_vault
+        .checked_add(effect.assets_to_fee)
+        .ok_or(crate::errors::VaultError::MathOverflow)?;
+    token.transfer(
+        assets_with_fee,
+        user_assets_account.as_ref(),
+        vault_assets_account.as_ref(),
+        assets_mint.as_ref(),
         authority.as_ref(),
-        spl_token_program.as_ref(),
+        &[],
     )?;
 
-    spl_mint_shares(
+    token.mint(
         effect.shares_to_user,
-        &user_shares_account,
-        &shares_mint,
-        authority.as_ref(),
-        spl_token_program.as_ref(),
+        user_shares_account.as_ref(),
+        shares_mint.as_ref(),
+        shares_mint_authority.as_ref(),
+        shares_mint_authority_seeds_with_bump!(vault_info.as_ref().key, shares_mint_authority_bump),
     )?;
 
     Ok(())
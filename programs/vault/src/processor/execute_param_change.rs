@@ -0,0 +1,32 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::ExecuteParamChangeContext,
+    operations::vault_apply_param_change,
+    processor::{clock_unix_timestamp, close_program_account},
+    utils::guards::require,
+};
+
+pub fn process_execute_param_change(accounts: &[AccountInfo]) -> ProgramResult {
+    let ExecuteParamChangeContext {
+        vault_info,
+        pending_change,
+        rent_receiver,
+    } = ExecuteParamChangeContext::load(accounts)?;
+
+    let change = {
+        let pending = pending_change.get()?;
+        require!(
+            pending.is_ready(clock_unix_timestamp()?),
+            crate::errors::VaultError::TimelockNotExpired.into()
+        );
+        pending.change()?
+    };
+
+    {
+        let mut vault = vault_info.get_mut()?;
+        vault_apply_param_change(&mut vault, change)?;
+    }
+
+    close_program_account(pending_change.as_ref(), &rent_receiver)
+}
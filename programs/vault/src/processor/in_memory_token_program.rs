@@ -0,0 +1,285 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_option::COption, program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::{
+    error::TokenError,
+    state::{Account as TokenAccount, Mint},
+};
+
+use crate::utils::guards::{require, require_eq};
+
+use super::TokenProgram;
+
+/// Pure Rust token ledger over the SPL state stored in the accounts
+///
+/// Applies the balance, supply and authority rules of SPL Token without a
+/// runtime, so that processors run in native tests with real bookkeeping.
+pub struct InMemoryTokenProgram;
+
+impl InMemoryTokenProgram {
+    /// Checks that `authority` signed, directly or as the PDA of `signer_seeds`
+    fn check_signed(authority: &AccountInfo, signer_seeds: &[&[u8]]) -> ProgramResult {
+        if signer_seeds.is_empty() {
+            require!(authority.is_signer, ProgramError::MissingRequiredSignature);
+        } else {
+            let signer = Pubkey::create_program_address(signer_seeds, &crate::ID)?;
+            require_eq!(
+                &signer,
+                authority.key,
+                ProgramError::MissingRequiredSignature
+            );
+        }
+        Ok(())
+    }
+
+    /// Debits `amount` from `account` on behalf of its owner or delegate
+    fn debit(account: &mut TokenAccount, authority: &Pubkey, amount: u64) -> ProgramResult {
+        if &account.owner != authority {
+            require_eq!(
+                account.delegate,
+                COption::Some(*authority),
+                TokenError::OwnerMismatch.into()
+            );
+            account.delegated_amount = account
+                .delegated_amount
+                .checked_sub(amount)
+                .ok_or(TokenError::InsufficientFunds)?;
+            if account.delegated_amount == 0 {
+                account.delegate = COption::None;
+            }
+        }
+        account.amount = account
+            .amount
+            .checked_sub(amount)
+            .ok_or(TokenError::InsufficientFunds)?;
+        Ok(())
+    }
+
+    fn load_account(info: &AccountInfo, mint: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+        let account = TokenAccount::unpack(&info.try_borrow_data()?)?;
+        require_eq!(&account.mint, mint.key, TokenError::MintMismatch.into());
+        Ok(account)
+    }
+
+    fn store<T: Pack>(state: T, info: &AccountInfo) -> ProgramResult {
+        T::pack(state, &mut info.try_borrow_mut_data()?)
+    }
+}
+
+impl TokenProgram for InMemoryTokenProgram {
+    fn transfer<'a>(
+        &self,
+        amount: u64,
+        from: &AccountInfo<'a>,
+        to: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        Self::check_signed(authority, signer_seeds)?;
+        let mut source = Self::load_account(from, mint)?;
+        let mut destination = Self::load_account(to, mint)?;
+
+        Self::debit(&mut source, authority.key, amount)?;
+        destination.amount = destination
+            .amount
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+
+        Self::store(source, from)?;
+        Self::store(destination, to)
+    }
+
+    fn mint<'a>(
+        &self,
+        amount: u64,
+        to: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        mint_authority: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        Self::check_signed(mint_authority, signer_seeds)?;
+        let mut mint_state = Mint::unpack(&mint.try_borrow_data()?)?;
+        require_eq!(
+            mint_state.mint_authority,
+            COption::Some(*mint_authority.key),
+            TokenError::OwnerMismatch.into()
+        );
+        let mut destination = Self::load_account(to, mint)?;
+
+        mint_state.supply = mint_state
+            .supply
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+        destination.amount = destination
+            .amount
+            .checked_add(amount)
+            .ok_or(TokenError::Overflow)?;
+
+        Self::store(mint_state, mint)?;
+        Self::store(destination, to)
+    }
+
+    fn burn<'a>(
+        &self,
+        amount: u64,
+        from: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        Self::check_signed(authority, &[])?;
+        let mut mint_state = Mint::unpack(&mint.try_borrow_data()?)?;
+        let mut source = Self::load_account(from, mint)?;
+
+        Self::debit(&mut source, authority.key, amount)?;
+        mint_state.supply = mint_state
+            .supply
+            .checked_sub(amount)
+            .ok_or(TokenError::Overflow)?;
+
+        Self::store(mint_state, mint)?;
+        Self::store(source, from)
+    }
+
+    fn balance(&self, account: &AccountInfo) -> Result<u64, ProgramError> {
+        Ok(TokenAccount::unpack(&account.try_borrow_data()?)?.amount)
+    }
+
+    fn supply(&self, mint: &AccountInfo) -> Result<u64, ProgramError> {
+        Ok(Mint::unpack(&mint.try_borrow_data()?)?.supply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operations::Slippage,
+        processor::{
+            process_deposit, process_redeem_shares,
+            test_utils::{infos, set_processor_stubs, TestAccount},
+        },
+        shares_mint_authority_seeds,
+        state::{Vault, VAULT_DISCRIMINATOR, VAULT_VERSION},
+        vault_assets_account_seeds,
+    };
+
+    #[test]
+    fn test_deposit_and_redeem_in_memory() {
+        set_processor_stubs();
+
+        let vault_pk = Pubkey::new_unique();
+        let (vault_assets_pk, vault_assets_account_bump) =
+            Pubkey::find_program_address(vault_assets_account_seeds!(vault_pk), &crate::ID);
+        let (mint_authority_pk, shares_mint_authority_bump) =
+            Pubkey::find_program_address(shares_mint_authority_seeds!(vault_pk), &crate::ID);
+        let assets_mint_pk = Pubkey::new_unique();
+        let shares_mint_pk = Pubkey::new_unique();
+        let alice_pk = Pubkey::new_unique();
+
+        let vault = Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            shares_mint: shares_mint_pk,
+            assets_mint: assets_mint_pk,
+            vault_assets_account: vault_assets_pk,
+            vault_assets_account_bump,
+            shares_mint_authority_bump,
+            ..Default::default()
+        };
+        let mut vault_account =
+            TestAccount::new(vault_pk, crate::ID, bytemuck::bytes_of(&vault).to_vec());
+        let mut vault_assets =
+            TestAccount::token_account(vault_assets_pk, assets_mint_pk, vault_assets_pk, 0);
+        let mut assets_mint = TestAccount::mint(assets_mint_pk, Pubkey::new_unique(), 1_000);
+        let mut shares_mint = TestAccount::mint(shares_mint_pk, mint_authority_pk, 0);
+        let mut alice = TestAccount::signer(alice_pk);
+        let mut alice_assets =
+            TestAccount::token_account(Pubkey::new_unique(), assets_mint_pk, alice_pk, 1_000);
+        let mut alice_shares =
+            TestAccount::token_account(Pubkey::new_unique(), shares_mint_pk, alice_pk, 0);
+        let mut token_program = TestAccount::new(spl_token::ID, Pubkey::default(), vec![]);
+        let mut mint_authority = TestAccount::new(mint_authority_pk, crate::ID, vec![]);
+
+        process_deposit(
+            &InMemoryTokenProgram,
+            &infos(&mut [
+                &mut vault_account,
+                &mut vault_assets,
+                &mut assets_mint,
+                &mut shares_mint,
+                &mut alice_assets,
+                &mut alice,
+                &mut alice_shares,
+                &mut token_program,
+                &mut mint_authority,
+            ]),
+            100,
+            Slippage::default(),
+        )
+        .unwrap();
+
+        let shares = alice_shares.amount();
+        assert!(shares > 0);
+        assert_eq!(alice_assets.amount(), 900);
+        assert_eq!(vault_assets.amount(), 100);
+        assert_eq!(shares_mint.supply(), shares);
+        assert_eq!(u64::from(vault_account.vault().shares), shares);
+        assert_eq!(u64::from(vault_account.vault().assets), 100);
+
+        // -- alice cannot deposit more assets than she holds, the runtime
+        // -- would roll back the vault
+        let vault_data = vault_account.data.clone();
+        assert_eq!(
+            process_deposit(
+                &InMemoryTokenProgram,
+                &infos(&mut [
+                    &mut vault_account,
+                    &mut vault_assets,
+                    &mut assets_mint,
+                    &mut shares_mint,
+                    &mut alice_assets,
+                    &mut alice,
+                    &mut alice_shares,
+                    &mut token_program,
+                    &mut mint_authority,
+                ]),
+                901,
+                Slippage::default(),
+            ),
+            Err(TokenError::InsufficientFunds.into())
+        );
+        vault_account.data = vault_data;
+
+        process_redeem_shares(
+            &InMemoryTokenProgram,
+            &infos(&mut [
+                &mut vault_account,
+                &mut vault_assets,
+                &mut assets_mint,
+                &mut shares_mint,
+                &mut alice_shares,
+                &mut alice,
+                &mut alice_assets,
+                &mut token_program,
+            ]),
+            shares,
+            Slippage::default(),
+        )
+        .unwrap();
+
+        assert_eq!(alice_shares.amount(), 0);
+        assert_eq!(shares_mint.supply(), 0);
+        assert_eq!(u64::from(vault_account.vault().shares), 0);
+        assert_eq!(
+            alice_assets.amount() + vault_assets.amount(),
+            1_000,
+            "assets are conserved"
+        );
+        assert_eq!(
+            u64::from(vault_account.vault().assets),
+            vault_assets.amount()
+        );
+    }
+}
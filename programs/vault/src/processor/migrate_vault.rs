@@ -0,0 +1,44 @@
+use std::mem::size_of;
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError, rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    loaders::MigrateVaultContext, operations::vault_migrate, processor::system_transfer,
+    state::Vault,
+};
+
+pub fn process_migrate_vault(accounts: &[AccountInfo]) -> ProgramResult {
+    let MigrateVaultContext {
+        vault_info,
+        authority: _,
+        payer,
+        system_program,
+    } = MigrateVaultContext::load(accounts)?;
+
+    let vault_info = vault_info.as_ref();
+    let new_len = size_of::<Vault>();
+
+    // -- extend the account to the current layout, keeping it rent exempt
+    if vault_info.data_len() < new_len {
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(vault_info.lamports());
+        if rent_due > 0 {
+            system_transfer(
+                payer.as_ref(),
+                vault_info,
+                rent_due,
+                system_program.as_ref(),
+            )?;
+        }
+        vault_info.realloc(new_len, true)?;
+    }
+
+    let mut data = vault_info.try_borrow_mut_data()?;
+    vault_migrate(&mut data).map_err(|e| -> ProgramError { e.into() })?;
+
+    Ok(())
+}
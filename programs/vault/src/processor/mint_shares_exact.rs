@@ -0,0 +1,67 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::{
+    loaders::MintSharesContext,
+    operations::{vault_accrue_management_fee, vault_mint_shares_exact, Slippage},
+    processor::{clock_slot, clock_unix_timestamp, TokenProgram},
+    shares_mint_authority_seeds_with_bump,
+};
+
+pub fn process_mint_shares_exact<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    amount: u64,
+    slippage: Slippage,
+) -> ProgramResult {
+    let MintSharesContext {
+        vault_info,
+        vault_assets_account,
+        assets_mint,
+        shares_mint,
+        user_assets_account,
+        authority,
+        user_shares_account,
+        spl_token_program: _,
+        shares_mint_authority,
+        user_position,
+    } = MintSharesContext::load(accounts)?;
+
+    let shares_mint_authority_bump = vault_info.get()?.shares_mint_authority_bump;
+
+    slippage.check_deadline(clock_slot()?)?;
+
+    let now = clock_unix_timestamp()?;
+    let effect = {
+        let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, now)?;
+        vault_mint_shares_exact(&mut vault, amount).map_err(|e| -> ProgramError { e.into() })?
+    };
+    slippage.check(&effect)?;
+
+    if let Some(user_position) = &user_position {
+        user_position
+            .get_mut()?
+            .record_deposit(effect.shares_to_user, now)?;
+    }
+
+    token.transfer(
+        effect.assets_to_vault,
+        user_assets_account.as_ref(),
+        vault_assets_account.as_ref(),
+        assets_mint.as_ref(),
+        authority.as_ref(),
+        &[],
+    )?;
+
+    token.mint(
+        effect.shares_to_user,
+        user_shares_account.as_ref(),
+        shares_mint.as_ref(),
+        shares_mint_authority.as_ref(),
+        shares_mint_authority_seeds_with_bump!(vault_info.as_ref().key, shares_mint_authority_bump),
+    )?;
+
+    Ok(())
+}
@@ -1,18 +1,60 @@
+pub mod accept_authority;
+pub mod cancel_authority;
+pub mod cancel_param_change;
+pub mod collect_fee;
+pub mod create_vault;
 pub mod deposit;
 pub mod deposit_exact;
 pub mod deposit_with_fee;
 pub mod deposit_with_fee_exact;
+pub mod execute_param_change;
+pub mod migrate_vault;
+pub mod mint_shares_exact;
+pub mod open_position;
+pub mod preview;
+pub mod process_instruction;
+pub mod propose_authority;
+pub mod queue_param_change;
 pub mod redeem_shares;
+pub mod set_roles;
+pub mod set_status;
 pub mod slash;
 pub mod update_reward;
+pub mod withdraw_assets;
 
+#[cfg(not(target_os = "solana"))]
+pub mod in_memory_token_program;
 pub mod spl_token_utils;
+pub mod system_utils;
+#[cfg(test)]
+pub mod test_utils;
+pub mod token_program;
 
+pub use accept_authority::*;
+pub use cancel_authority::*;
+pub use cancel_param_change::*;
+pub use collect_fee::*;
+pub use create_vault::*;
 pub use deposit::*;
 pub use deposit_exact::*;
 pub use deposit_with_fee::*;
 pub use deposit_with_fee_exact::*;
+pub use execute_param_change::*;
+#[cfg(not(target_os = "solana"))]
+pub use in_memory_token_program::*;
+pub use migrate_vault::*;
+pub use mint_shares_exact::*;
+pub use open_position::*;
+pub use preview::*;
+pub use process_instruction::*;
+pub use propose_authority::*;
+pub use queue_param_change::*;
 pub use redeem_shares::*;
+pub use set_roles::*;
+pub use set_status::*;
 pub use slash::*;
 pub use spl_token_utils::*;
+pub use system_utils::*;
+pub use token_program::*;
 pub use update_reward::*;
+pub use withdraw_assets::*;
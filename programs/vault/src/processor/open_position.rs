@@ -0,0 +1,104 @@
+use std::mem::size_of;
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::OpenPositionContext,
+    processor::{clock_unix_timestamp, system_create_pda_account},
+    state::{UserPosition, VaultAction, USER_POSITION_DISCRIMINATOR},
+    user_position_seeds_with_bump,
+};
+
+pub fn process_open_position(accounts: &[AccountInfo]) -> ProgramResult {
+    let OpenPositionContext {
+        vault_info,
+        user_position,
+        owner,
+        payer,
+        system_program,
+        user_position_bump,
+    } = OpenPositionContext::load(accounts)?;
+
+    // -- a position is only useful to deposit
+    vault_info.get()?.require_allows(VaultAction::Deposit)?;
+
+    let vault_pk = vault_info.as_ref().key;
+    let owner_pk = owner.as_ref().key;
+
+    // -- allocate the PDA position account, owned by the program
+    system_create_pda_account(
+        payer.as_ref(),
+        user_position.as_ref(),
+        size_of::<UserPosition>(),
+        &crate::ID,
+        system_program.as_ref(),
+        &[user_position_seeds_with_bump!(
+            vault_pk,
+            owner_pk,
+            user_position_bump
+        )],
+    )?;
+
+    let mut position = user_position.get_uninitialized_mut()?;
+    position.discriminator = USER_POSITION_DISCRIMINATOR;
+    position.vault = *vault_pk;
+    position.owner = *owner_pk;
+    position.shares = 0.into();
+    position.last_deposit_ts = clock_unix_timestamp()?.into();
+    position.bump = user_position_bump;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        processor::test_utils::{set_processor_stubs, TestAccount},
+        state::{Vault, VAULT_DISCRIMINATOR, VAULT_VERSION},
+        user_position_seeds,
+    };
+    use solana_program::{pubkey::Pubkey, system_program};
+
+    #[test]
+    fn test_open_position_prefunded() {
+        set_processor_stubs();
+
+        let (vault_pk, owner_pk) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            assets_mint: Pubkey::new_unique(),
+            shares_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let (position_pk, _) =
+            Pubkey::find_program_address(user_position_seeds!(vault_pk, owner_pk), &crate::ID);
+
+        let mut vault_account =
+            TestAccount::new(vault_pk, crate::ID, bytemuck::bytes_of(&vault).to_vec());
+        // -- someone sent a lamport to the PDA to block the position
+        let mut position_account = TestAccount::new(position_pk, system_program::ID, vec![]);
+        position_account.lamports = 1;
+        let mut owner = TestAccount::signer(owner_pk);
+        owner.lamports = 1_000_000_000;
+        let mut system = TestAccount::new(system_program::ID, Pubkey::default(), vec![]);
+
+        let owner_info = owner.info();
+        let accounts = [
+            vault_account.info(),
+            position_account.info(),
+            owner_info.clone(),
+            owner_info,
+            system.info(),
+        ];
+        process_open_position(&accounts).unwrap();
+
+        let position_info = &accounts[1];
+        assert_eq!(position_info.owner, &crate::ID);
+        let data = position_info.try_borrow_data().unwrap();
+        let position = bytemuck::from_bytes::<UserPosition>(&data);
+        assert!(position.is_initialized());
+        assert_eq!(position.owner, owner_pk);
+    }
+}
@@ -0,0 +1,45 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data,
+    program_error::ProgramError,
+};
+
+use crate::{
+    instruction::PreviewKind, loaders::PreviewContext, operations::vault_accrue_management_fee,
+};
+
+use super::{clock_unix_timestamp, TokenProgram};
+
+/// Quote an operation without executing it
+///
+/// The quote is returned as a little-endian `u64` via `set_return_data`.
+pub fn process_preview<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    kind: PreviewKind,
+    amount: u64,
+) -> ProgramResult {
+    let PreviewContext {
+        vault_info,
+        user_shares_account,
+    } = PreviewContext::load(accounts)?;
+
+    // -- quote against the vault as if the management fee were accrued now
+    let mut vault = *vault_info.get()?;
+    vault_accrue_management_fee(&mut vault, clock_unix_timestamp()?)?;
+
+    let quote = match kind {
+        PreviewKind::Deposit => vault.preview_deposit(amount)?,
+        PreviewKind::Mint => vault.preview_mint(amount)?,
+        PreviewKind::Withdraw => vault.preview_withdraw(amount)?,
+        PreviewKind::Redeem => vault.preview_redeem(amount)?,
+        PreviewKind::MaxDeposit => vault.max_deposit(),
+        PreviewKind::MaxRedeem => {
+            let user_shares_account =
+                user_shares_account.ok_or(ProgramError::NotEnoughAccountKeys)?;
+            vault.max_redeem(token.balance(user_shares_account.as_ref())?)
+        }
+    };
+
+    set_return_data(&quote.to_le_bytes());
+    Ok(())
+}
@@ -0,0 +1,87 @@
+// -- `require_eq!` drops its error under certora, leaving `ProgramError` unused
+#[cfg_attr(feature = "certora", allow(unused_imports))]
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    instruction::{
+        CertoraVaultInstruction, CreateVaultArgs, PreviewKind, ProposeAuthorityArgs,
+        QueueParamChangeArgs, SetRolesArgs, SlippageArgs, VaultInstructionData,
+    },
+    state::{AuthorityRole, VaultStatus},
+    utils::guards::require_eq,
+};
+
+use super::*;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    require_eq!(program_id, &crate::ID, ProgramError::IncorrectProgramId);
+
+    let VaultInstructionData {
+        instruction,
+        amount,
+        args,
+    } = VaultInstructionData::unpack(instruction_data)?;
+    let token = &DefaultTokenProgram {};
+
+    match instruction {
+        CertoraVaultInstruction::CreateVault => {
+            process_create_vault(accounts, CreateVaultArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::Deposit => {
+            process_deposit(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::DepositWithFee => {
+            process_deposit_with_fee(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::DepositExact => {
+            process_deposit_exact(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::DepositWithFeeExact => {
+            process_deposit_with_fee_exact(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::RedeemShares => {
+            process_redeem_shares(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::UpdateReward => process_update_reward(token, accounts),
+        CertoraVaultInstruction::Slash => process_slash(token, accounts, amount),
+        CertoraVaultInstruction::CollectFee => process_collect_fee(token, accounts),
+        CertoraVaultInstruction::MigrateVault => process_migrate_vault(accounts),
+        CertoraVaultInstruction::MintSharesExact => {
+            process_mint_shares_exact(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::WithdrawAssets => {
+            process_withdraw_assets(token, accounts, amount, SlippageArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::Preview => {
+            process_preview(token, accounts, PreviewKind::unpack(args)?, amount)
+        }
+        CertoraVaultInstruction::OpenPosition => process_open_position(accounts),
+        CertoraVaultInstruction::QueueParamChange => {
+            process_queue_param_change(accounts, QueueParamChangeArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::ExecuteParamChange => process_execute_param_change(accounts),
+        CertoraVaultInstruction::CancelParamChange => process_cancel_param_change(accounts),
+        CertoraVaultInstruction::ProposeAuthority => {
+            process_propose_authority(accounts, ProposeAuthorityArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::AcceptAuthority => {
+            process_accept_authority(accounts, AuthorityRole::unpack(args)?)
+        }
+        CertoraVaultInstruction::CancelAuthority => {
+            process_cancel_authority(accounts, AuthorityRole::unpack(args)?)
+        }
+        CertoraVaultInstruction::SetRoles => {
+            process_set_roles(accounts, SetRolesArgs::unpack(args)?)
+        }
+        CertoraVaultInstruction::SetStatus => {
+            process_set_status(accounts, VaultStatus::unpack(args)?)
+        }
+    }
+}
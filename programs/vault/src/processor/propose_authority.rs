@@ -0,0 +1,22 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    instruction::ProposeAuthorityArgs, loaders::AuthorityHolderContext,
+    operations::vault_propose_authority,
+};
+
+pub fn process_propose_authority(
+    accounts: &[AccountInfo],
+    args: &ProposeAuthorityArgs,
+) -> ProgramResult {
+    let role = args.role()?;
+    let AuthorityHolderContext {
+        vault_info,
+        authority: _,
+    } = AuthorityHolderContext::load(accounts, role)?;
+
+    let mut vault = vault_info.get_mut()?;
+    vault_propose_authority(&mut vault, role, args.new_authority)?;
+
+    Ok(())
+}
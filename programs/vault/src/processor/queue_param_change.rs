@@ -0,0 +1,133 @@
+use std::mem::size_of;
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    instruction::QueueParamChangeArgs,
+    loaders::QueueParamChangeContext,
+    operations::vault_check_param_change,
+    pending_change_seeds_with_bump,
+    processor::{clock_unix_timestamp, system_create_pda_account},
+    state::{PendingChange, PARAM_CHANGE_DELAY, PENDING_CHANGE_DISCRIMINATOR},
+    VaultError,
+};
+
+pub fn process_queue_param_change(
+    accounts: &[AccountInfo],
+    args: &QueueParamChangeArgs,
+) -> ProgramResult {
+    let change = args.change()?;
+    let (kind, value) = change.encode();
+
+    let QueueParamChangeContext {
+        vault_info,
+        pending_change,
+        authority: _,
+        payer,
+        system_program,
+        pending_change_bump,
+    } = QueueParamChangeContext::load(accounts, kind)?;
+
+    {
+        let vault = vault_info.get()?;
+        vault_check_param_change(&vault, &change)?;
+    }
+
+    let vault_pk = vault_info.as_ref().key;
+
+    // -- allocate the PDA pending change, owned by the program
+    system_create_pda_account(
+        payer.as_ref(),
+        pending_change.as_ref(),
+        size_of::<PendingChange>(),
+        &crate::ID,
+        system_program.as_ref(),
+        &[pending_change_seeds_with_bump!(
+            vault_pk,
+            kind as u8,
+            pending_change_bump
+        )],
+    )?;
+
+    let execute_after = clock_unix_timestamp()?
+        .checked_add(PARAM_CHANGE_DELAY)
+        .ok_or(VaultError::MathOverflow)?;
+
+    let mut pending = pending_change.get_uninitialized_mut()?;
+    pending.discriminator = PENDING_CHANGE_DISCRIMINATOR;
+    pending.vault = *vault_pk;
+    pending.kind = kind as u8;
+    pending.value = value;
+    pending.execute_after = execute_after.into();
+    pending.rent_receiver = *payer.as_ref().key;
+    pending.bump = pending_change_bump;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pending_change_seeds,
+        processor::test_utils::{set_processor_stubs, TestAccount},
+        state::{ParamChange, Vault, VAULT_DISCRIMINATOR, VAULT_VERSION},
+    };
+    use solana_program::{pubkey::Pubkey, rent::Rent, system_program};
+
+    #[test]
+    fn test_queue_param_change_prefunded() {
+        set_processor_stubs();
+
+        let (vault_pk, admin_pk) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let vault = Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            admin: admin_pk,
+            assets_mint: Pubkey::new_unique(),
+            shares_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let change = ParamChange::FeeBps(100);
+        let (kind, value) = change.encode();
+        let (pending_pk, _) =
+            Pubkey::find_program_address(pending_change_seeds!(vault_pk, kind as u8), &crate::ID);
+
+        let mut vault_account =
+            TestAccount::new(vault_pk, crate::ID, bytemuck::bytes_of(&vault).to_vec());
+        // -- someone sent a lamport to the PDA to block the change
+        let mut pending_account = TestAccount::new(pending_pk, system_program::ID, vec![]);
+        pending_account.lamports = 1;
+        let mut admin = TestAccount::signer(admin_pk);
+        let mut payer = TestAccount::signer(Pubkey::new_unique());
+        payer.lamports = 1_000_000_000;
+        let mut system = TestAccount::new(system_program::ID, Pubkey::default(), vec![]);
+
+        let accounts = [
+            vault_account.info(),
+            pending_account.info(),
+            admin.info(),
+            payer.info(),
+            system.info(),
+        ];
+        let args = QueueParamChangeArgs {
+            kind: kind as u8,
+            value,
+        };
+        process_queue_param_change(&accounts, &args).unwrap();
+
+        let pending_info = &accounts[1];
+        assert_eq!(pending_info.owner, &crate::ID);
+        let rent = Rent::default().minimum_balance(size_of::<PendingChange>());
+        assert_eq!(pending_info.lamports(), rent);
+        assert_eq!(accounts[3].lamports(), 1_000_000_000 - (rent - 1));
+
+        let data = pending_info.try_borrow_data().unwrap();
+        let pending = bytemuck::from_bytes::<PendingChange>(&data);
+        assert!(pending.is_initialized());
+        assert_eq!(
+            ParamChange::decode(pending.kind, &pending.value),
+            Ok(change)
+        );
+    }
+}
@@ -2,12 +2,17 @@ use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
 
 use crate::{
     loaders::RedeemSharesContext,
-    operations::vault_redeem_shares,
-    processor::{spl_burn_shares, spl_transfer_assets_from_vault},
-    utils::guards::require_ne,
+    operations::{vault_accrue_management_fee, vault_redeem_shares, Slippage},
+    processor::{clock_slot, clock_unix_timestamp, TokenProgram},
+    vault_assets_account_seeds_with_bump,
 };
 
-pub fn process_redeem_shares(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+pub fn process_redeem_shares<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    amount: u64,
+    slippage: Slippage,
+) -> ProgramResult {
     let context = RedeemSharesContext::load(accounts)?;
     let RedeemSharesContext {
         vault_info,
@@ -17,34 +22,47 @@ pub fn process_redeem_shares(accounts: &[AccountInfo], amount: u64) -> ProgramRe
         user_shares_account,
         authority,
         user_assets_account,
-        spl_token_program,
+        spl_token_program: _,
+        user_position,
     } = context;
 
-    require_ne!(
-        vault_assets_account.key,
-        user_assets_account.key,
-        crate::errors::VaultError::SelfTransfer.into()
-    );
+    let vault_assets_account_bump = vault_info.get()?.vault_assets_account_bump;
+
+    slippage.check_deadline(clock_slot()?)?;
+
+    let now = clock_unix_timestamp()?;
+    let holding = match &user_position {
+        Some(user_position) => Some(user_position.get()?.holding(now)),
+        None => None,
+    };
 
     let effect = {
         let mut vault = vault_info.get_mut()?;
-        vault_redeem_shares(&mut vault, amount)?
+        vault_accrue_management_fee(&mut vault, now)?;
+        vault_redeem_shares(&mut vault, amount, holding)?
     };
+    slippage.check(&effect)?;
+
+    if let Some(user_position) = &user_position {
+        user_position
+            .get_mut()?
+            .record_withdrawal(effect.shares_to_burn);
+    }
 
-    spl_burn_shares(
+    token.burn(
         effect.shares_to_burn,
-        &user_shares_account,
-        &shares_mint,
+        user_shares_account.as_ref(),
+        shares_mint.as_ref(),
         authority.as_ref(),
-        spl_token_program.as_ref(),
     )?;
 
-    spl_transfer_assets_from_vault(
+    token.transfer(
         effect.assets_to_user,
-        &vault_assets_account,
-        &user_assets_account,
-        &assets_mint,
-        spl_token_program.as_ref(),
+        vault_assets_account.as_ref(),
+        user_assets_account.as_ref(),
+        assets_mint.as_ref(),
+        vault_assets_account.as_ref(),
+        vault_assets_account_seeds_with_bump!(vault_info.as_ref().key, vault_assets_account_bump),
     )?;
 
     Ok(())
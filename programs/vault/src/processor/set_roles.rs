@@ -0,0 +1,15 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{instruction::SetRolesArgs, loaders::SetRolesContext, operations::vault_set_roles};
+
+pub fn process_set_roles(accounts: &[AccountInfo], args: &SetRolesArgs) -> ProgramResult {
+    let SetRolesContext {
+        vault_info,
+        authority: _,
+    } = SetRolesContext::load(accounts)?;
+
+    let mut vault = vault_info.get_mut()?;
+    vault_set_roles(&mut vault, args.authority, args.roles)?;
+
+    Ok(())
+}
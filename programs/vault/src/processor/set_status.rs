@@ -0,0 +1,21 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::SetStatusContext,
+    operations::{vault_accrue_management_fee, vault_set_status},
+    processor::clock_unix_timestamp,
+    state::VaultStatus,
+};
+
+pub fn process_set_status(accounts: &[AccountInfo], status: VaultStatus) -> ProgramResult {
+    let SetStatusContext {
+        vault_info,
+        authority: _,
+    } = SetStatusContext::load(accounts, status)?;
+
+    let mut vault = vault_info.get_mut()?;
+    vault_accrue_management_fee(&mut vault, clock_unix_timestamp()?)?;
+    vault_set_status(&mut vault, status)?;
+
+    Ok(())
+}
@@ -1,30 +1,42 @@
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
 
-use crate::{loaders::SlashContext, operations::vault_process_slash};
+use crate::{
+    loaders::SlashContext,
+    operations::{vault_accrue_management_fee, vault_process_slash},
+    vault_assets_account_seeds_with_bump,
+};
 
-use super::spl_transfer_assets_from_vault;
+use super::{clock_unix_timestamp, TokenProgram};
 
-pub fn process_slash(accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+pub fn process_slash<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
     let SlashContext {
         vault_info,
         vault_assets_account,
         user_token_account,
         assets_mint,
         authority: _,
-        spl_token_program,
+        spl_token_program: _,
     } = SlashContext::load(accounts)?;
 
+    let vault_assets_account_bump = vault_info.get()?.vault_assets_account_bump;
+
     let effects = {
         let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, clock_unix_timestamp()?)?;
         vault_process_slash(&mut vault, amount)?
     };
 
-    spl_transfer_assets_from_vault(
+    token.transfer(
         effects.assets_to_user,
         vault_assets_account.as_ref(),
-        &user_token_account,
-        &assets_mint,
-        spl_token_program.as_ref(),
+        user_token_account.as_ref(),
+        assets_mint.as_ref(),
+        vault_assets_account.as_ref(),
+        vault_assets_account_seeds_with_bump!(vault_info.as_ref().key, vault_assets_account_bump),
     )?;
 
     Ok(())
@@ -0,0 +1,165 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+    sysvar::Sysvar,
+};
+
+/// Current unix timestamp from the `Clock` sysvar
+#[cfg_attr(feature = "certora", 
+    cvlr::mock_fn(with=crate::certora::mocks::processor::clock_unix_timestamp))]
+pub fn clock_unix_timestamp() -> Result<i64, solana_program::program_error::ProgramError> {
+    Ok(solana_program::clock::Clock::get()?.unix_timestamp)
+}
+
+/// Current slot from the `Clock` sysvar
+#[cfg_attr(feature = "certora",
+    cvlr::mock_fn(with=crate::certora::mocks::processor::clock_slot))]
+pub fn clock_slot() -> Result<u64, solana_program::program_error::ProgramError> {
+    Ok(solana_program::clock::Clock::get()?.slot)
+}
+
+/// Create a rent-exempt account of `space` bytes owned by `owner`
+///
+/// `signer_seeds` are required when `new_account` is a PDA
+pub fn system_create_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    system_program: &AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            owner,
+        ),
+        &[payer.clone(), new_account.clone(), system_program.clone()],
+        signer_seeds,
+    )
+}
+
+/// Create a rent-exempt PDA of `space` bytes owned by `owner`
+///
+/// Unlike [`system_create_account`] this succeeds when the address already
+/// holds lamports, e.g. sent by someone to block the creation: only the rent
+/// still due is transferred before the account is allocated and assigned.
+pub fn system_create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    system_program: &AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let rent_due = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(new_account.lamports());
+    if rent_due > 0 {
+        system_transfer(payer, new_account, rent_due, system_program)?;
+    }
+
+    let accounts = [new_account.clone(), system_program.clone()];
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space as u64),
+        &accounts,
+        signer_seeds,
+    )?;
+    invoke_signed(
+        &system_instruction::assign(new_account.key, owner),
+        &accounts,
+        signer_seeds,
+    )
+}
+
+pub fn system_transfer<'a>(
+    from: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    lamports: u64,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    invoke(
+        &system_instruction::transfer(from.key, to.key, lamports),
+        &[from.clone(), to.clone(), system_program.clone()],
+    )
+}
+
+/// Close an account owned by the program, sending its rent to `receiver`
+pub fn close_program_account<'a>(
+    account: &AccountInfo<'a>,
+    receiver: &AccountInfo<'a>,
+) -> ProgramResult {
+    let lamports = receiver
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **receiver.try_borrow_mut_lamports()? = lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.realloc(0, false)?;
+    account.assign(&system_program::ID);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        processor::test_utils::{set_processor_stubs, TestAccount},
+        vault_assets_account_seeds, vault_assets_account_seeds_with_bump,
+    };
+    use solana_program::program_pack::Pack;
+
+    #[test]
+    fn test_create_pda_account_rent_already_paid() {
+        set_processor_stubs();
+
+        let vault_pk = Pubkey::new_unique();
+        let (pda, bump) =
+            Pubkey::find_program_address(vault_assets_account_seeds!(vault_pk), &crate::ID);
+        let space = spl_token::state::Account::LEN;
+        let rent = Rent::default().minimum_balance(space);
+
+        let mut new_account = TestAccount::new(pda, system_program::ID, vec![]);
+        new_account.lamports = rent + 1;
+        let mut payer = TestAccount::signer(Pubkey::new_unique());
+        let mut system = TestAccount::new(system_program::ID, Pubkey::default(), vec![]);
+        let (new_info, payer_info, system_info) = (new_account.info(), payer.info(), system.info());
+
+        // -- without the seeds the PDA cannot sign its allocation
+        assert_eq!(
+            system_create_pda_account(
+                &payer_info,
+                &new_info,
+                space,
+                &spl_token::ID,
+                &system_info,
+                &[]
+            ),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        system_create_pda_account(
+            &payer_info,
+            &new_info,
+            space,
+            &spl_token::ID,
+            &system_info,
+            &[vault_assets_account_seeds_with_bump!(vault_pk, bump)],
+        )
+        .unwrap();
+
+        assert_eq!(new_info.owner, &spl_token::ID);
+        assert_eq!(new_info.data_len(), space);
+        assert_eq!(new_info.lamports(), rent + 1);
+        assert_eq!(payer_info.lamports(), 0);
+    }
+}
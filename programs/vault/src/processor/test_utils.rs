@@ -0,0 +1,234 @@
+//! Accounts and syscall stubs to run processors in native tests
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+use crate::state::Vault;
+
+/// Serves a default `Clock` and `Rent` to the processors and runs the
+/// system program instructions they invoke
+///
+/// The stubs are global, every test running processors installs the same.
+struct ProcessorStubs;
+
+impl ProcessorStubs {
+    fn account<'a, 'info>(
+        account_infos: &'a [AccountInfo<'info>],
+        key: &Pubkey,
+    ) -> Result<&'a AccountInfo<'info>, ProgramError> {
+        account_infos
+            .iter()
+            .find(|info| info.key == key)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    }
+
+    /// Checks that `account` signed, directly or as a PDA of the program
+    fn check_signed(account: &AccountInfo, signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+        let signed = account.is_signer
+            || signers_seeds.iter().any(|seeds| {
+                Pubkey::create_program_address(seeds, &crate::ID).as_ref() == Ok(account.key)
+            });
+        if signed {
+            Ok(())
+        } else {
+            Err(ProgramError::MissingRequiredSignature)
+        }
+    }
+
+    fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+        let from_lamports = from
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        let to_lamports = to
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **from.try_borrow_mut_lamports()? = from_lamports;
+        **to.try_borrow_mut_lamports()? = to_lamports;
+        Ok(())
+    }
+
+    fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+        if !account.data_is_empty() || !system_program::check_id(account.owner) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        // -- the data outlives the test account, leaking it is fine in tests
+        *account.try_borrow_mut_data()? = Box::leak(vec![0; space as usize].into_boxed_slice());
+        Ok(())
+    }
+
+    fn assign(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+        if !system_program::check_id(account.owner) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        account.assign(owner);
+        Ok(())
+    }
+
+    fn invoke_system(
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let instruction_data = limited_deserialize(&instruction.data, 1024)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let first = Self::account(account_infos, &instruction.accounts[0].pubkey)?;
+        Self::check_signed(first, signers_seeds)?;
+
+        match instruction_data {
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => {
+                let new_account = Self::account(account_infos, &instruction.accounts[1].pubkey)?;
+                Self::check_signed(new_account, signers_seeds)?;
+                // -- the system program refuses any funded address
+                if new_account.lamports() > 0 {
+                    return Err(ProgramError::AccountAlreadyInitialized);
+                }
+                Self::transfer(first, new_account, lamports)?;
+                Self::allocate(new_account, space)?;
+                Self::assign(new_account, &owner)
+            }
+            SystemInstruction::Transfer { lamports } => {
+                let to = Self::account(account_infos, &instruction.accounts[1].pubkey)?;
+                Self::transfer(first, to, lamports)
+            }
+            SystemInstruction::Allocate { space } => Self::allocate(first, space),
+            SystemInstruction::Assign { owner } => Self::assign(first, &owner),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+impl SyscallStubs for ProcessorStubs {
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if system_program::check_id(&instruction.program_id) {
+            Self::invoke_system(instruction, account_infos, signers_seeds)
+        } else {
+            Err(ProgramError::IncorrectProgramId)
+        }
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+}
+
+pub fn set_processor_stubs() {
+    set_syscall_stubs(Box::new(ProcessorStubs));
+}
+
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key,
+            owner,
+            lamports: 0,
+            data,
+            is_signer: false,
+        }
+    }
+
+    pub fn signer(key: Pubkey) -> Self {
+        Self {
+            is_signer: true,
+            ..Self::new(key, system_program::ID, vec![])
+        }
+    }
+
+    pub fn packed<T: Pack>(key: Pubkey, state: T) -> Self {
+        let mut data = vec![0; T::LEN];
+        T::pack(state, &mut data).unwrap();
+        Self::new(key, spl_token::ID, data)
+    }
+
+    pub fn mint(key: Pubkey, authority: Pubkey, supply: u64) -> Self {
+        Self::packed(
+            key,
+            Mint {
+                mint_authority: COption::Some(authority),
+                supply,
+                is_initialized: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> Self {
+        Self::packed(
+            key,
+            TokenAccount {
+                mint,
+                owner,
+                amount,
+                state: AccountState::Initialized,
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn info(&mut self) -> AccountInfo {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+
+    pub fn amount(&self) -> u64 {
+        TokenAccount::unpack(&self.data).unwrap().amount
+    }
+
+    pub fn supply(&self) -> u64 {
+        Mint::unpack(&self.data).unwrap().supply
+    }
+
+    pub fn vault(&self) -> &Vault {
+        bytemuck::from_bytes(&self.data)
+    }
+}
+
+pub fn infos<'a>(accounts: &'a mut [&'a mut TestAccount]) -> Vec<AccountInfo<'a>> {
+    accounts.iter_mut().map(|account| account.info()).collect()
+}
@@ -0,0 +1,144 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke_signed,
+    program_error::ProgramError, program_pack::Pack,
+};
+
+use super::spl_mint_decimals;
+
+/// Token movements of the processors
+///
+/// Implemented by [`SplTokenProgram`] with CPIs into SPL Token, by the
+/// Certora mocks, and by [`super::InMemoryTokenProgram`] to run processors in
+/// native tests. Transfers and mints are signed by `authority`, or by the
+/// program for the PDA derived from `signer_seeds` when they are not empty.
+pub trait TokenProgram {
+    fn transfer<'a>(
+        &self,
+        amount: u64,
+        from: &AccountInfo<'a>,
+        to: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult;
+
+    fn mint<'a>(
+        &self,
+        amount: u64,
+        to: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        mint_authority: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult;
+
+    /// Burns `amount` tokens, signed by the owner or delegate of `from`
+    fn burn<'a>(
+        &self,
+        amount: u64,
+        from: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+    ) -> ProgramResult;
+
+    fn balance(&self, account: &AccountInfo) -> Result<u64, ProgramError>;
+
+    fn supply(&self, mint: &AccountInfo) -> Result<u64, ProgramError>;
+}
+
+/// Backend of [`super::process_instruction`]
+#[cfg(not(feature = "certora"))]
+pub type DefaultTokenProgram = SplTokenProgram;
+
+/// Backend of [`super::process_instruction`]
+#[cfg(feature = "certora")]
+pub type DefaultTokenProgram = crate::certora::mocks::processor::CvlrTokenProgram;
+
+/// CPIs into the SPL Token program, checked by the loaders
+pub struct SplTokenProgram;
+
+/// Signers of `invoke_signed`, none when `signer_seeds` is empty
+fn signers<'s, 't>(signer_seeds: &'s &'t [&'t [u8]]) -> &'s [&'t [&'t [u8]]] {
+    if signer_seeds.is_empty() {
+        &[]
+    } else {
+        std::slice::from_ref(signer_seeds)
+    }
+}
+
+impl TokenProgram for SplTokenProgram {
+    fn transfer<'a>(
+        &self,
+        amount: u64,
+        from: &AccountInfo<'a>,
+        to: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        invoke_signed(
+            &spl_token::instruction::transfer_checked(
+                &spl_token::ID,
+                from.key,
+                mint.key,
+                to.key,
+                authority.key,
+                &[],
+                amount,
+                spl_mint_decimals(mint)?,
+            )?,
+            &[from.clone(), mint.clone(), to.clone(), authority.clone()],
+            signers(&signer_seeds),
+        )
+    }
+
+    fn mint<'a>(
+        &self,
+        amount: u64,
+        to: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        mint_authority: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        invoke_signed(
+            &spl_token::instruction::mint_to(
+                &spl_token::ID,
+                mint.key,
+                to.key,
+                mint_authority.key,
+                &[],
+                amount,
+            )?,
+            &[mint.clone(), to.clone(), mint_authority.clone()],
+            signers(&signer_seeds),
+        )
+    }
+
+    fn burn<'a>(
+        &self,
+        amount: u64,
+        from: &AccountInfo<'a>,
+        mint: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        invoke_signed(
+            &spl_token::instruction::burn(
+                &spl_token::ID,
+                from.key,
+                mint.key,
+                authority.key,
+                &[],
+                amount,
+            )?,
+            &[from.clone(), mint.clone(), authority.clone()],
+            &[],
+        )
+    }
+
+    fn balance(&self, account: &AccountInfo) -> Result<u64, ProgramError> {
+        Ok(spl_token::state::Account::unpack(&account.try_borrow_data()?)?.amount)
+    }
+
+    fn supply(&self, mint: &AccountInfo) -> Result<u64, ProgramError> {
+        Ok(spl_token::state::Mint::unpack(&mint.try_borrow_data()?)?.supply)
+    }
+}
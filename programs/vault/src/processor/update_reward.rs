@@ -1,24 +1,32 @@
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
 
-use crate::{loaders::UpdateRewardContext, operations::vault_update_reward};
+use crate::{
+    loaders::UpdateRewardContext,
+    operations::{vault_accrue_management_fee, vault_update_reward},
+};
 
-use super::spl_token_account_amount;
+use super::{clock_unix_timestamp, TokenProgram};
 
-pub fn process_update_reward(accounts: &[AccountInfo]) -> ProgramResult {
+pub fn process_update_reward<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
     let context = UpdateRewardContext::load(accounts)?;
 
-    // This instruction is permissionless. Anyone can run it to update vault state.
+    // Only holders of the reward updater role can crank the vault.
     // IRL it should be limited to once per epoch
 
     let UpdateRewardContext {
         vault_info,
         vault_assets_account,
+        authority: _,
     } = context;
 
-    let vault_asset_account_amount = spl_token_account_amount(&vault_assets_account)?;
+    let vault_asset_account_amount = token.balance(vault_assets_account.as_ref())?;
 
     let _effect = {
         let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, clock_unix_timestamp()?)?;
         vault_update_reward(&mut vault, vault_asset_account_amount)?
     };
 
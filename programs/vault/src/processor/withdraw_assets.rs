@@ -0,0 +1,69 @@
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+
+use crate::{
+    loaders::WithdrawAssetsContext,
+    operations::{vault_accrue_management_fee, vault_withdraw_assets, Slippage},
+    processor::{clock_slot, clock_unix_timestamp, TokenProgram},
+    vault_assets_account_seeds_with_bump,
+};
+
+pub fn process_withdraw_assets<T: TokenProgram>(
+    token: &T,
+    accounts: &[AccountInfo],
+    amount: u64,
+    slippage: Slippage,
+) -> ProgramResult {
+    let context = WithdrawAssetsContext::load(accounts)?;
+    let WithdrawAssetsContext {
+        vault_info,
+        vault_assets_account,
+        assets_mint,
+        shares_mint,
+        user_shares_account,
+        authority,
+        user_assets_account,
+        spl_token_program: _,
+        user_position,
+    } = context;
+
+    let vault_assets_account_bump = vault_info.get()?.vault_assets_account_bump;
+
+    slippage.check_deadline(clock_slot()?)?;
+
+    let now = clock_unix_timestamp()?;
+    let holding = match &user_position {
+        Some(user_position) => Some(user_position.get()?.holding(now)),
+        None => None,
+    };
+
+    let effect = {
+        let mut vault = vault_info.get_mut()?;
+        vault_accrue_management_fee(&mut vault, now)?;
+        vault_withdraw_assets(&mut vault, amount, holding)?
+    };
+    slippage.check(&effect)?;
+
+    if let Some(user_position) = &user_position {
+        user_position
+            .get_mut()?
+            .record_withdrawal(effect.shares_to_burn);
+    }
+
+    token.burn(
+        effect.shares_to_burn,
+        user_shares_account.as_ref(),
+        shares_mint.as_ref(),
+        authority.as_ref(),
+    )?;
+
+    token.transfer(
+        effect.assets_to_user,
+        vault_assets_account.as_ref(),
+        user_assets_account.as_ref(),
+        assets_mint.as_ref(),
+        vault_assets_account.as_ref(),
+        vault_assets_account_seeds_with_bump!(vault_info.as_ref().key, vault_assets_account_bump),
+    )?;
+
+    Ok(())
+}
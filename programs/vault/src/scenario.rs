@@ -0,0 +1,321 @@
+//! Scenario files replayed on the [`crate::simulator`]
+//!
+//! A scenario is a JSON document with an initial vault and the steps to run
+//! on it. A step may state the effect it must return in `expect`, or the name
+//! of the error it must fail with in `expect_error`. Steps without either
+//! must succeed.
+//!
+//! ```json
+//! {
+//!   "description": "Bob front-runs Alice with a donation",
+//!   "vault": { "shares": 0, "assets": 0 },
+//!   "steps": [
+//!     { "op": "fund", "user": "bob", "assets": 10001 },
+//!     { "op": "deposit", "user": "bob", "assets": 1,
+//!       "expect": { "shares_to_user": 1, "assets_to_vault": 1 } },
+//!     { "op": "redeem", "user": "bob", "shares": 2, "expect_error": "MathOverflow" }
+//!   ]
+//! }
+//! ```
+//!
+//! Vault fields left out are zero, as are effect fields. The vault fields of
+//! a Certora counterexample can be copied as they are logged.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    operations::VaultEffect,
+    simulator::{SimResult, SimStep, VaultSimulator},
+    state::{ExitFeeTier, Vault, EXIT_FEE_TIERS, VAULT_DISCRIMINATOR, VAULT_VERSION},
+};
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScenarioExitFeeTier {
+    pub min_assets: u64,
+    pub fee_bps: u64,
+}
+
+/// Initial state of the vault, the bookkeeping fields of [`Vault`]
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScenarioVault {
+    pub shares: u64,
+    pub assets: u64,
+    pub fee_bps: u64,
+    pub fee_amount: u64,
+    pub decimals_offset: u8,
+    pub management_fee_bps: u64,
+    pub last_fee_accrual_ts: i64,
+    pub performance_fee_bps: u64,
+    pub high_water_mark_assets: u64,
+    pub high_water_mark_shares: u64,
+    pub exit_fee_tiers: Vec<ScenarioExitFeeTier>,
+    pub exit_fee_holding_period: i64,
+    pub exit_fee_held_bps: u64,
+    pub status: u8,
+}
+
+impl ScenarioVault {
+    pub fn to_vault(&self) -> Result<Vault, String> {
+        if self.exit_fee_tiers.len() > EXIT_FEE_TIERS {
+            return Err(format!("at most {EXIT_FEE_TIERS} exit fee tiers"));
+        }
+        let mut exit_fee_tiers = [ExitFeeTier::default(); EXIT_FEE_TIERS];
+        for (tier, scenario_tier) in exit_fee_tiers.iter_mut().zip(&self.exit_fee_tiers) {
+            *tier = ExitFeeTier {
+                min_assets: scenario_tier.min_assets.into(),
+                fee_bps: scenario_tier.fee_bps.into(),
+            };
+        }
+
+        let vault = Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            shares: self.shares.into(),
+            assets: self.assets.into(),
+            fee_bps: self.fee_bps.into(),
+            fee_amount: self.fee_amount.into(),
+            decimals_offset: self.decimals_offset,
+            management_fee_bps: self.management_fee_bps.into(),
+            last_fee_accrual_ts: self.last_fee_accrual_ts.into(),
+            performance_fee_bps: self.performance_fee_bps.into(),
+            high_water_mark_assets: self.high_water_mark_assets.into(),
+            high_water_mark_shares: self.high_water_mark_shares.into(),
+            exit_fee_tiers,
+            exit_fee_holding_period: self.exit_fee_holding_period.into(),
+            exit_fee_held_bps: self.exit_fee_held_bps.into(),
+            status: self.status,
+            // -- mints are not simulated, distinct keys keep `validate` happy
+            assets_mint: Pubkey::new_from_array([1; 32]),
+            shares_mint: Pubkey::new_from_array([2; 32]),
+            ..Default::default()
+        };
+
+        let invalid = |e| format!("invalid vault: {e:?}");
+        vault.validate().map_err(invalid)?;
+        vault.validate_exit_fee().map_err(invalid)?;
+        vault.checked_gross_assets().map_err(invalid)?;
+        Ok(vault)
+    }
+}
+
+/// A step and its expected outcome
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ScenarioStep {
+    #[serde(flatten)]
+    pub step: SimStep,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<VaultEffect>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_error: Option<String>,
+}
+
+impl ScenarioStep {
+    fn expected(&self) -> Result<Outcome, String> {
+        match (&self.expect, &self.expect_error) {
+            (Some(effect), None) => Ok(Outcome::Effect(*effect)),
+            (None, Some(name)) => Ok(Outcome::Error(name.clone())),
+            (None, None) => Ok(Outcome::Success),
+            (Some(_), Some(_)) => Err(format!("{:?}: both expect and expect_error", self.step)),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    #[serde(default)]
+    pub description: String,
+    pub vault: ScenarioVault,
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// Outcome of a step, as expected or as run
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Outcome {
+    Success,
+    Effect(VaultEffect),
+    Error(String),
+}
+
+impl Outcome {
+    fn of(result: &SimResult<VaultEffect>) -> Self {
+        match result {
+            Ok(effect) => Outcome::Effect(*effect),
+            Err(e) => Outcome::Error(e.name()),
+        }
+    }
+
+    fn matches(&self, actual: &Outcome) -> bool {
+        match self {
+            Outcome::Success => matches!(actual, Outcome::Effect(_)),
+            _ => self == actual,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Success => write!(f, "success"),
+            Outcome::Effect(effect) => write!(f, "{effect:?}"),
+            Outcome::Error(name) => write!(f, "error {name}"),
+        }
+    }
+}
+
+/// A step whose outcome differs from the expected one
+#[derive(Clone, Debug)]
+pub struct Mismatch {
+    /// Index of the step in the scenario
+    pub index: usize,
+    pub step: SimStep,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "step {}: {:?}", self.index, self.step)?;
+        writeln!(f, "  - {}", self.expected)?;
+        write!(f, "  + {}", self.actual)
+    }
+}
+
+impl Scenario {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Runs every step and returns the ones that did not go as expected
+    ///
+    /// Fails when the scenario itself is invalid.
+    pub fn run(&self) -> Result<Vec<Mismatch>, String> {
+        let mut sim = VaultSimulator::new(self.vault.to_vault()?);
+        let mut mismatches = vec![];
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let expected = step.expected()?;
+            let actual = Outcome::of(&sim.step(step.step.clone()));
+            if !expected.matches(&actual) {
+                mismatches.push(Mismatch {
+                    index,
+                    step: step.step.clone(),
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::Path};
+
+    /// Replays the regression scenarios of `scenarios/`
+    #[test]
+    fn test_scenario_files() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("scenarios");
+        let mut count = 0;
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let scenario = Scenario::from_json(&fs::read_to_string(&path).unwrap()).unwrap();
+            let mismatches = scenario.run().unwrap();
+            assert!(
+                mismatches.is_empty(),
+                "{}:\n{}",
+                path.display(),
+                mismatches
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn test_scenario_reports_mismatches() {
+        let scenario = Scenario::from_json(
+            r#"{
+                "vault": { "shares": 100, "assets": 100 },
+                "steps": [
+                    { "op": "fund", "user": "alice", "assets": 50 },
+                    { "op": "deposit", "user": "alice", "assets": 50,
+                      "expect": { "shares_to_user": 49, "assets_to_vault": 50 } },
+                    { "op": "redeem", "user": "alice", "shares": 60,
+                      "expect_error": "InsufficientFunds" },
+                    { "op": "slash", "receiver": "bob", "assets": 1000 },
+                    { "op": "collect_fee", "expect_error": "VaultPaused" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let mismatches = scenario.run().unwrap();
+        let indices: Vec<_> = mismatches.iter().map(|m| m.index).collect();
+        assert_eq!(indices, [1, 3, 4]);
+        assert_eq!(
+            mismatches[0].actual,
+            format!(
+                "{:?}",
+                VaultEffect {
+                    shares_to_user: 50,
+                    assets_to_vault: 50,
+                    ..Default::default()
+                }
+            )
+        );
+        assert_eq!(mismatches[1].expected, "success");
+        assert_eq!(mismatches[1].actual, "error MathOverflow");
+    }
+
+    #[test]
+    fn test_scenario_invalid_vault() {
+        for vault in [
+            r#"{ "shares": 1, "assets": 18446744073709551615, "fee_amount": 1 }"#,
+            r#"{ "decimals_offset": 30 }"#,
+            r#"{ "status": 9 }"#,
+            r#"{ "exit_fee_tiers": [{ "min_assets": 10, "fee_bps": 1 }, { "min_assets": 5 }] }"#,
+        ] {
+            let json = format!(r#"{{ "vault": {vault}, "steps": [] }}"#);
+            let scenario = Scenario::from_json(&json).unwrap();
+            assert!(scenario.run().unwrap_err().starts_with("invalid vault"));
+        }
+    }
+
+    #[test]
+    fn test_scenario_roundtrip() {
+        let scenario = Scenario {
+            description: String::new(),
+            vault: ScenarioVault::default(),
+            steps: vec![ScenarioStep {
+                step: SimStep::Advance { seconds: 10 },
+                expect: Some(VaultEffect::default()),
+                expect_error: None,
+            }],
+        };
+        let json = scenario.to_json().unwrap();
+        let parsed = Scenario::from_json(&json).unwrap();
+
+        assert_eq!(parsed.steps[0].step, scenario.steps[0].step);
+        assert_eq!(parsed.steps[0].expect, scenario.steps[0].expect);
+        assert!(
+            Scenario::from_json(r#"{ "vault": { "shares": 1, "sharez": 2 }, "steps": [] }"#)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,632 @@
+//! In-memory vault simulator
+//!
+//! Replays sequences of vault operations by several users natively. Each step
+//! runs the operation its processor runs, moves tokens between the users, the
+//! vault assets account and the fee account as the processor does, and checks
+//! that no asset or share is created or lost along the way.
+
+use std::collections::BTreeMap;
+
+use spl_token::error::TokenError;
+
+use crate::{
+    operations::{
+        vault_accrue_management_fee, vault_collect_fee, vault_deposit_assets,
+        vault_deposit_assets_with_fee, vault_mint_shares_exact, vault_process_slash,
+        vault_redeem_shares, vault_update_reward, vault_withdraw_assets, VaultEffect,
+    },
+    state::Vault,
+    VaultError,
+};
+
+/// Holder of the shares issued before the simulation starts
+pub const GENESIS: &str = "genesis";
+
+/// Error of a step, from the vault or from the token program, or a step
+/// that went through but broke [`SimState::check_conservation`]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SimError {
+    Vault(VaultError),
+    Token(TokenError),
+    Conservation(&'static str),
+}
+
+impl SimError {
+    /// Name of the error variant, e.g. `MathOverflow`
+    pub fn name(&self) -> String {
+        match self {
+            SimError::Vault(e) => format!("{e:?}"),
+            SimError::Token(e) => format!("{e:?}"),
+            SimError::Conservation(violation) => format!("ConservationViolation: {violation}"),
+        }
+    }
+}
+
+impl From<VaultError> for SimError {
+    fn from(e: VaultError) -> Self {
+        SimError::Vault(e)
+    }
+}
+
+impl From<TokenError> for SimError {
+    fn from(e: TokenError) -> Self {
+        SimError::Token(e)
+    }
+}
+
+pub type SimResult<T> = Result<T, SimError>;
+
+/// Token balances of a user
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Balances {
+    pub assets: u64,
+    pub shares: u64,
+}
+
+/// A step of a simulation
+///
+/// Vault steps accrue the management fee first, like their processors.
+/// Redemptions ignore user positions and pay the full exit fee.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "scenario",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum SimStep {
+    /// Mints `assets` to `user`, outside of the vault
+    Fund {
+        user: String,
+        assets: u64,
+    },
+    Deposit {
+        user: String,
+        assets: u64,
+    },
+    DepositWithFee {
+        user: String,
+        assets: u64,
+    },
+    MintShares {
+        user: String,
+        shares: u64,
+    },
+    Redeem {
+        user: String,
+        shares: u64,
+    },
+    Withdraw {
+        user: String,
+        assets: u64,
+    },
+    /// Transfers `assets` from `user` straight to the vault assets account
+    Donate {
+        user: String,
+        assets: u64,
+    },
+    /// Mints `assets` of yield to the vault assets account
+    Yield {
+        assets: u64,
+    },
+    UpdateReward,
+    /// Slashes `assets` from the vault to `receiver`
+    Slash {
+        receiver: String,
+        assets: u64,
+    },
+    CollectFee,
+    /// Moves the clock forward by `seconds`
+    Advance {
+        seconds: i64,
+    },
+}
+
+/// Balances of every account of the simulation
+#[derive(Clone)]
+pub struct SimState {
+    pub vault: Vault,
+    pub users: BTreeMap<String, Balances>,
+    /// Balance of the vault assets account
+    pub vault_assets: u64,
+    /// Balance of the account fees are collected to
+    pub fee_account: u64,
+    /// Supply of the assets mint
+    pub assets_supply: u64,
+    /// Supply of the shares mint
+    pub shares_supply: u64,
+    /// Unix timestamp of the clock
+    pub now: i64,
+}
+
+/// State after a step, and what the step returned
+#[derive(Clone)]
+pub struct SimSnapshot {
+    pub step: SimStep,
+    pub result: SimResult<VaultEffect>,
+    pub state: SimState,
+}
+
+fn debit(balance: &mut u64, amount: u64) -> SimResult<()> {
+    *balance = balance
+        .checked_sub(amount)
+        .ok_or(TokenError::InsufficientFunds)?;
+    Ok(())
+}
+
+fn credit(balance: &mut u64, amount: u64) -> SimResult<()> {
+    *balance = balance.checked_add(amount).ok_or(TokenError::Overflow)?;
+    Ok(())
+}
+
+impl SimState {
+    /// Starts from `vault`, its assets account holding exactly the assets and
+    /// fees of the vault, and its shares held by [`GENESIS`]
+    pub fn new(vault: Vault) -> Self {
+        let mut users = BTreeMap::new();
+        if vault.num_shares() > 0 {
+            users.insert(
+                GENESIS.to_string(),
+                Balances {
+                    assets: 0,
+                    shares: vault.num_shares(),
+                },
+            );
+        }
+        Self {
+            users,
+            vault_assets: vault.gross_assets(),
+            fee_account: 0,
+            assets_supply: vault.gross_assets(),
+            shares_supply: vault.num_shares(),
+            now: vault.last_fee_accrual_ts(),
+            vault,
+        }
+    }
+
+    /// Balances of `user`, zero for users not seen yet
+    pub fn user(&self, user: &str) -> Balances {
+        self.users.get(user).copied().unwrap_or_default()
+    }
+
+    fn user_mut(&mut self, user: &str) -> &mut Balances {
+        self.users.entry(user.to_string()).or_default()
+    }
+
+    /// Applies `step`, leaving the state partially updated on error
+    fn apply(&mut self, step: &SimStep) -> SimResult<VaultEffect> {
+        match step {
+            SimStep::Fund { user, assets } => {
+                credit(&mut self.assets_supply, *assets)?;
+                credit(&mut self.user_mut(user).assets, *assets)?;
+                Ok(VaultEffect::default())
+            }
+            SimStep::Deposit { user, assets } => {
+                self.accrue()?;
+                let effect = vault_deposit_assets(&mut self.vault, *assets)?;
+                self.settle_deposit(user, &effect)
+            }
+            SimStep::DepositWithFee { user, assets } => {
+                self.accrue()?;
+                let effect = vault_deposit_assets_with_fee(&mut self.vault, *assets)?;
+                self.settle_deposit(user, &effect)
+            }
+            SimStep::MintShares { user, shares } => {
+                self.accrue()?;
+                let effect = vault_mint_shares_exact(&mut self.vault, *shares)?;
+                self.settle_deposit(user, &effect)
+            }
+            SimStep::Redeem { user, shares } => {
+                self.accrue()?;
+                let effect = vault_redeem_shares(&mut self.vault, *shares, None)?;
+                self.settle_redeem(user, &effect)
+            }
+            SimStep::Withdraw { user, assets } => {
+                self.accrue()?;
+                let effect = vault_withdraw_assets(&mut self.vault, *assets, None)?;
+                self.settle_redeem(user, &effect)
+            }
+            SimStep::Donate { user, assets } => {
+                debit(&mut self.user_mut(user).assets, *assets)?;
+                credit(&mut self.vault_assets, *assets)?;
+                Ok(VaultEffect::default())
+            }
+            SimStep::Yield { assets } => {
+                credit(&mut self.assets_supply, *assets)?;
+                credit(&mut self.vault_assets, *assets)?;
+                Ok(VaultEffect::default())
+            }
+            SimStep::UpdateReward => {
+                self.accrue()?;
+                Ok(vault_update_reward(&mut self.vault, self.vault_assets)?)
+            }
+            SimStep::Slash { receiver, assets } => {
+                self.accrue()?;
+                let effect = vault_process_slash(&mut self.vault, *assets)?;
+                debit(&mut self.vault_assets, effect.assets_to_user)?;
+                credit(&mut self.user_mut(receiver).assets, effect.assets_to_user)?;
+                Ok(effect)
+            }
+            SimStep::CollectFee => {
+                self.accrue()?;
+                let effect = vault_collect_fee(&mut self.vault)?;
+                debit(&mut self.vault_assets, effect.assets_to_user)?;
+                credit(&mut self.fee_account, effect.assets_to_user)?;
+                Ok(effect)
+            }
+            SimStep::Advance { seconds } => {
+                self.now = self
+                    .now
+                    .checked_add(*seconds)
+                    .ok_or(VaultError::MathOverflow)?;
+                Ok(VaultEffect::default())
+            }
+        }
+    }
+
+    fn accrue(&mut self) -> SimResult<()> {
+        vault_accrue_management_fee(&mut self.vault, self.now)?;
+        Ok(())
+    }
+
+    /// Token movements of the deposit processors, fees included
+    fn settle_deposit(&mut self, user: &str, effect: &VaultEffect) -> SimResult<VaultEffect> {
+        let assets = effect
+            .assets_to_vault
+            .checked_add(effect.assets_to_fee)
+            .ok_or(VaultError::MathOverflow)?;
+        debit(&mut self.user_mut(user).assets, assets)?;
+        credit(&mut self.vault_assets, assets)?;
+        credit(&mut self.shares_supply, effect.shares_to_user)?;
+        credit(&mut self.user_mut(user).shares, effect.shares_to_user)?;
+        Ok(*effect)
+    }
+
+    /// Token movements of the redeem processors, the exit fee stays in the vault
+    fn settle_redeem(&mut self, user: &str, effect: &VaultEffect) -> SimResult<VaultEffect> {
+        debit(&mut self.user_mut(user).shares, effect.shares_to_burn)?;
+        debit(&mut self.shares_supply, effect.shares_to_burn)?;
+        debit(&mut self.vault_assets, effect.assets_to_user)?;
+        credit(&mut self.user_mut(user).assets, effect.assets_to_user)?;
+        Ok(*effect)
+    }
+
+    /// Checks that tokens are conserved and that the vault is backed
+    ///
+    /// Every asset and share is held by some account, the shares mint matches
+    /// the vault bookkeeping, and the vault assets account covers the vault
+    /// assets and accrued fees. Donations and yield may add more until the
+    /// next reward update.
+    pub fn check_conservation(&self) -> Result<(), &'static str> {
+        let held_assets = self
+            .users
+            .values()
+            .map(|balances| u128::from(balances.assets))
+            .sum::<u128>()
+            + u128::from(self.vault_assets)
+            + u128::from(self.fee_account);
+        let held_shares = self
+            .users
+            .values()
+            .map(|balances| u128::from(balances.shares))
+            .sum::<u128>();
+
+        if held_assets != u128::from(self.assets_supply) {
+            return Err("assets held differ from the assets supply");
+        }
+        if held_shares != u128::from(self.shares_supply) {
+            return Err("shares held differ from the shares supply");
+        }
+        if self.shares_supply != self.vault.num_shares() {
+            return Err("shares supply differs from the vault shares");
+        }
+        if self.vault.gross_assets() > self.vault_assets {
+            return Err("vault assets account does not cover the vault assets and fees");
+        }
+        Ok(())
+    }
+}
+
+/// Runs steps over a [`SimState`], keeping a snapshot after each of them
+pub struct VaultSimulator {
+    state: SimState,
+    snapshots: Vec<SimSnapshot>,
+}
+
+impl VaultSimulator {
+    pub fn new(vault: Vault) -> Self {
+        Self {
+            state: SimState::new(vault),
+            snapshots: vec![],
+        }
+    }
+
+    pub fn state(&self) -> &SimState {
+        &self.state
+    }
+
+    pub fn vault(&self) -> &Vault {
+        &self.state.vault
+    }
+
+    pub fn user(&self, user: &str) -> Balances {
+        self.state.user(user)
+    }
+
+    pub fn snapshots(&self) -> &[SimSnapshot] {
+        &self.snapshots
+    }
+
+    /// Runs `step`, all or nothing like a transaction
+    ///
+    /// A step breaking [`SimState::check_conservation`] fails with
+    /// [`SimError::Conservation`] and is rolled back, so that the following
+    /// steps still run.
+    pub fn step(&mut self, step: SimStep) -> SimResult<VaultEffect> {
+        let mut state = self.state.clone();
+        let result = state.apply(&step).and_then(|effect| {
+            state.check_conservation().map_err(SimError::Conservation)?;
+            Ok(effect)
+        });
+        if result.is_ok() {
+            self.state = state;
+        }
+
+        self.snapshots.push(SimSnapshot {
+            step,
+            result: result.clone(),
+            state: self.state.clone(),
+        });
+        result
+    }
+
+    pub fn fund(&mut self, user: &str, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Fund {
+            user: user.to_string(),
+            assets,
+        })
+    }
+
+    pub fn deposit(&mut self, user: &str, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Deposit {
+            user: user.to_string(),
+            assets,
+        })
+    }
+
+    pub fn deposit_with_fee(&mut self, user: &str, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::DepositWithFee {
+            user: user.to_string(),
+            assets,
+        })
+    }
+
+    pub fn mint_shares(&mut self, user: &str, shares: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::MintShares {
+            user: user.to_string(),
+            shares,
+        })
+    }
+
+    pub fn redeem(&mut self, user: &str, shares: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Redeem {
+            user: user.to_string(),
+            shares,
+        })
+    }
+
+    pub fn withdraw(&mut self, user: &str, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Withdraw {
+            user: user.to_string(),
+            assets,
+        })
+    }
+
+    pub fn donate(&mut self, user: &str, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Donate {
+            user: user.to_string(),
+            assets,
+        })
+    }
+
+    pub fn add_yield(&mut self, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Yield { assets })
+    }
+
+    pub fn update_reward(&mut self) -> SimResult<VaultEffect> {
+        self.step(SimStep::UpdateReward)
+    }
+
+    pub fn slash(&mut self, receiver: &str, assets: u64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Slash {
+            receiver: receiver.to_string(),
+            assets,
+        })
+    }
+
+    pub fn collect_fee(&mut self) -> SimResult<VaultEffect> {
+        self.step(SimStep::CollectFee)
+    }
+
+    pub fn advance(&mut self, seconds: i64) -> SimResult<VaultEffect> {
+        self.step(SimStep::Advance { seconds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        state::{VAULT_DISCRIMINATOR, VAULT_VERSION},
+        utils::math::SECONDS_PER_YEAR,
+    };
+
+    fn new_empty_vault() -> Vault {
+        let mut vault = Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            version: VAULT_VERSION,
+            ..Default::default()
+        };
+        vault.set_high_water_mark();
+        vault
+    }
+
+    /// Replays `rule_inflation_attack` with a donation large enough to round
+    /// Alice's deposit down to a single share
+    #[test]
+    fn test_donation_attack() {
+        let mut sim = VaultSimulator::new(new_empty_vault());
+        sim.fund("bob", 10_001).unwrap();
+        sim.fund("alice", 10_000).unwrap();
+
+        // -- Bob buys a share and inflates its price with a donation
+        assert_eq!(sim.deposit("bob", 1).unwrap().shares_to_user, 1);
+        sim.donate("bob", 10_000).unwrap();
+        sim.update_reward().unwrap();
+        assert_eq!(sim.vault().num_assets(), 10_001);
+
+        // -- Alice buys shares at the inflated price
+        assert_eq!(sim.deposit("alice", 10_000).unwrap().shares_to_user, 1);
+
+        // -- both redeem, the virtual share keeps part of the donation
+        sim.redeem("bob", 1).unwrap();
+        sim.redeem("alice", 1).unwrap();
+
+        let bob = sim.user("bob");
+        let alice = sim.user("alice");
+        assert!(bob.assets <= 10_001, "Bob did not make profit");
+        assert!(alice.assets < 10_000);
+        assert_eq!(bob.shares + alice.shares, 0);
+        assert_eq!(sim.state().vault_assets, 20_001 - bob.assets - alice.assets);
+
+        // -- the donation shows up in the vault once the reward is updated
+        let snapshots = sim.snapshots();
+        assert_eq!(snapshots.len(), 8);
+        assert_eq!(snapshots[3].state.vault_assets, 10_001);
+        assert_eq!(snapshots[3].state.vault.num_assets(), 1);
+        assert_eq!(snapshots[4].state.vault.num_assets(), 10_001);
+    }
+
+    #[test]
+    fn test_failed_step_is_rolled_back() {
+        let mut sim = VaultSimulator::new(new_empty_vault());
+        sim.fund("alice", 100).unwrap();
+        sim.deposit("alice", 100).unwrap();
+
+        assert_eq!(
+            sim.redeem("alice", 101),
+            Err(VaultError::MathOverflow.into())
+        );
+        assert_eq!(
+            sim.deposit("alice", 1),
+            Err(TokenError::InsufficientFunds.into())
+        );
+        assert_eq!(
+            sim.slash("alice", 101),
+            Err(VaultError::MathOverflow.into())
+        );
+
+        assert_eq!(
+            sim.user("alice"),
+            Balances {
+                assets: 0,
+                shares: 100
+            }
+        );
+        assert_eq!(sim.vault().num_shares(), 100);
+        assert_eq!(sim.vault().num_assets(), 100);
+        assert_eq!(sim.snapshots().len(), 5);
+        assert!(sim.snapshots()[4].result.is_err());
+    }
+
+    #[test]
+    fn test_conservation_violation_is_reported() {
+        let mut sim = VaultSimulator::new(new_empty_vault());
+        sim.fund("alice", 100).unwrap();
+        sim.deposit("alice", 100).unwrap();
+
+        // -- an asset leaks out of the vault assets account
+        sim.state.vault_assets -= 1;
+        let result = sim.fund("bob", 10);
+        assert!(matches!(result, Err(SimError::Conservation(_))));
+        assert!(result
+            .unwrap_err()
+            .name()
+            .starts_with("ConservationViolation"));
+        assert_eq!(sim.user("bob"), Balances::default());
+        assert_eq!(sim.snapshots().len(), 3);
+    }
+
+    /// Token shortfalls and vault guards fail with distinct errors
+    #[test]
+    fn test_token_and_vault_errors_are_distinct() {
+        let mut sim = VaultSimulator::new(new_empty_vault());
+        sim.fund("alice", 100).unwrap();
+        sim.fund("bob", 100).unwrap();
+        sim.deposit("alice", 100).unwrap();
+        sim.deposit("bob", 100).unwrap();
+
+        // -- the vault has the shares, alice does not
+        assert_eq!(
+            sim.redeem("alice", 101),
+            Err(TokenError::InsufficientFunds.into())
+        );
+        assert_eq!(
+            sim.redeem("alice", 201),
+            Err(VaultError::MathOverflow.into())
+        );
+        assert_eq!(sim.slash("alice", 100), Err(VaultError::GuardFail.into()));
+
+        assert_eq!(sim.user("alice").shares, 100);
+        assert_eq!(sim.vault().num_shares(), 200);
+        assert_eq!(sim.vault().num_assets(), 200);
+    }
+
+    #[test]
+    fn test_fees_and_slash_are_conserved() {
+        let mut vault = Vault {
+            fee_bps: 500u64.into(),
+            management_fee_bps: 200u64.into(),
+            performance_fee_bps: 1_000u64.into(),
+            ..new_empty_vault()
+        };
+        vault.set_high_water_mark();
+        let mut sim = VaultSimulator::new(vault);
+
+        sim.fund("alice", 10_000).unwrap();
+        let effect = sim.deposit_with_fee("alice", 10_000).unwrap();
+        assert_eq!(effect.assets_to_fee, 500);
+
+        sim.advance(SECONDS_PER_YEAR as i64).unwrap();
+        sim.add_yield(1_000).unwrap();
+        assert!(sim.update_reward().unwrap().assets_to_fee > 0);
+        sim.slash("slasher", 500).unwrap();
+
+        let fee = sim.collect_fee().unwrap().assets_to_user;
+        assert!(fee > 500);
+        assert_eq!(sim.state().fee_account, fee);
+        assert_eq!(sim.user("slasher").assets, 500);
+
+        let shares = sim.user("alice").shares;
+        sim.redeem("alice", shares).unwrap();
+        assert_eq!(sim.vault().num_shares(), 0);
+        assert_eq!(
+            sim.user("alice").assets + sim.state().vault_assets,
+            11_000 - 500 - fee
+        );
+    }
+
+    #[test]
+    fn test_genesis_holds_existing_shares() {
+        let vault = Vault {
+            shares: 100u64.into(),
+            assets: 150u64.into(),
+            fee_amount: 5u64.into(),
+            ..new_empty_vault()
+        };
+        let sim = VaultSimulator::new(vault);
+
+        assert_eq!(sim.user(GENESIS).shares, 100);
+        assert_eq!(sim.state().vault_assets, 155);
+        assert_eq!(sim.state().check_conservation(), Ok(()));
+    }
+}
@@ -1,17 +1,182 @@
+use std::mem::size_of;
+
 use bytemuck::{Pod, Zeroable};
+use num_enum::TryFromPrimitive;
 use solana_program::pubkey::{Pubkey, PubkeyError};
 
 use crate::utils::math::FeeBps;
 use crate::utils::{
-    guards::{require_gt, require_ne},
-    math::mul_div_floor,
+    guards::{require, require_eq, require_ge, require_gt, require_le, require_ne},
+    math::{mul_div_ceil, mul_div_floor},
 };
 use crate::{VaultError, VaultResult};
-use spl_pod::primitives::PodU64;
+use spl_pod::primitives::{PodI64, PodU64};
+
+/// Discriminator that marks an account as a [`Vault`]
+pub const VAULT_DISCRIMINATOR: [u8; 8] = *b"crtvault";
+
+/// Version of the [`Vault`] account layout
+pub const VAULT_VERSION: u8 = 8;
+
+/// Largest supported [`Vault::decimals_offset`]
+pub const MAX_DECIMALS_OFFSET: u8 = 9;
+
+/// Number of amount-based exit fee tiers
+pub const EXIT_FEE_TIERS: usize = 4;
+
+/// Number of entries in the [`Vault::roles`] table
+pub const ROLE_ENTRIES: usize = 4;
+
+/// Discriminator that marks an account as a [`UserPosition`]
+pub const USER_POSITION_DISCRIMINATOR: [u8; 8] = *b"crtposit";
+
+/// Discriminator that marks an account as a [`PendingChange`]
+pub const PENDING_CHANGE_DISCRIMINATOR: [u8; 8] = *b"crtpendc";
+
+/// Seconds between queueing a parameter change and executing it
+pub const PARAM_CHANGE_DELAY: i64 = 2 * 24 * 60 * 60;
+
+/// Hard cap on the deposit fee that a parameter change can raise `fee_bps` to
+pub const FEE_BPS_CAP: u64 = 1_000;
+
+/// Virtual assets added to the vault balance when converting
+const VIRTUAL_ASSETS: u64 = 1;
+
+/// Size of the [`Vault`] account data for each supported layout version
+///
+/// Layouts are append-only: a new version only adds fields at the end of
+/// [`Vault`], so the data of an older layout is a prefix of the newer one.
+pub const fn vault_layout_size(version: u8) -> Option<usize> {
+    match version {
+        1 => Some(235),
+        2 => Some(236),
+        3 => Some(252),
+        4 => Some(276),
+        5 => Some(356),
+        6 => Some(420),
+        7 => Some(552),
+        8 => Some(553),
+        _ => None,
+    }
+}
+
+const _: () =
+    assert!(matches!(vault_layout_size(VAULT_VERSION), Some(size) if size == size_of::<Vault>()));
+
+/// Checks the discriminator of raw vault data and returns its layout version
+pub fn vault_data_version(data: &[u8]) -> VaultResult<u8> {
+    let discriminator = data.get(..8).ok_or(VaultError::InvalidDiscriminator)?;
+    require!(discriminator != [0u8; 8], VaultError::UninitializedAccount);
+    require_eq!(
+        discriminator,
+        VAULT_DISCRIMINATOR,
+        VaultError::InvalidDiscriminator
+    );
+
+    let version = *data.get(8).ok_or(VaultError::InvalidDiscriminator)?;
+    require!(
+        vault_layout_size(version).is_some(),
+        VaultError::UnsupportedVersion
+    );
+    Ok(version)
+}
+
+/// Vault authorities that are rotated in two steps, see [`Vault::pending_admin`]
+#[repr(u8)]
+#[derive(TryFromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AuthorityRole {
+    Admin = 0,
+    SlashAdmin = 1,
+}
+
+/// Privileges checked by the loaders of privileged instructions
+#[repr(u8)]
+#[derive(TryFromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Role {
+    /// Held by [`Vault::admin`] only, it cannot be granted
+    Admin = 0,
+    /// Held by [`Vault::slash_admin`] and the authorities granted it
+    Slasher = 1,
+    FeeManager = 2,
+    Pauser = 3,
+    RewardUpdater = 4,
+}
+
+impl Role {
+    pub const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// Roles held by [`Vault::admin`] besides [`Role::Admin`], they follow the
+/// admin key through rotations
+pub const ADMIN_DEFAULT_ROLES: u8 =
+    Role::FeeManager.mask() | Role::Pauser.mask() | Role::RewardUpdater.mask();
+
+/// Roles of an authority, as a bitmask of [`Role::mask`]
+///
+/// An entry with the default authority is free.
+#[repr(C)]
+#[derive(Default, Pod, Copy, Clone, Zeroable, Debug, PartialEq)]
+pub struct RoleEntry {
+    pub authority: Pubkey,
+    pub roles: u8,
+}
+
+/// Lifecycle of a vault, set by the pauser and the admin
+#[repr(u8)]
+#[derive(TryFromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VaultStatus {
+    Active = 0,
+    /// Deposits are stopped, everything else keeps running
+    DepositsPaused = 1,
+    /// Everything is stopped until the vault is unpaused
+    FullyPaused = 2,
+    /// Final status, depositors can only leave and fees be collected
+    WindDown = 3,
+}
+
+/// Operations gated by the [`VaultStatus`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VaultAction {
+    Deposit,
+    Redeem,
+    CollectFee,
+    Slash,
+    UpdateReward,
+    /// Changes to parameters, roles and authorities
+    Configure,
+    /// Dropping a queued parameter change, allowed in every status so that a
+    /// change maturing during a pause does not run as soon as it ends
+    CancelParamChange,
+}
+
+impl VaultStatus {
+    pub fn allows(self, action: VaultAction) -> bool {
+        match self {
+            _ if action == VaultAction::CancelParamChange => true,
+            Self::Active => true,
+            Self::DepositsPaused => action != VaultAction::Deposit,
+            Self::FullyPaused => false,
+            Self::WindDown => matches!(action, VaultAction::Redeem | VaultAction::CollectFee),
+        }
+    }
+}
+
+/// Exit fee charged on withdrawals of at least `min_assets`
+#[repr(C)]
+#[derive(Default, Pod, Copy, Clone, Zeroable, Debug, PartialEq)]
+pub struct ExitFeeTier {
+    pub min_assets: PodU64,
+    pub fee_bps: PodU64,
+}
 
 #[repr(C)]
 #[derive(Default, Pod, Copy, Clone, Zeroable)]
 pub struct Vault {
+    pub discriminator: [u8; 8],
+    pub version: u8,
+
     pub admin: Pubkey,
     pub slash_admin: Pubkey,
     pub shares_mint: Pubkey,
@@ -26,6 +191,46 @@ pub struct Vault {
 
     pub vault_assets_account: Pubkey,
     pub vault_assets_account_bump: u8,
+
+    pub shares_mint_authority_bump: u8,
+
+    // -- layout 2
+    /// Shares are represented with `decimals_offset` more decimals than
+    /// assets, see [`Vault::virtual_shares_offset`]
+    pub decimals_offset: u8,
+
+    // -- layout 3
+    /// Annual management fee, accrued per second into `fee_amount`
+    pub management_fee_bps: PodU64,
+    pub last_fee_accrual_ts: PodI64,
+
+    // -- layout 4
+    /// Fee on the share price gain above the high-water mark
+    pub performance_fee_bps: PodU64,
+    /// Highest share price reached, as virtual assets per virtual shares
+    pub high_water_mark_assets: PodU64,
+    pub high_water_mark_shares: PodU64,
+
+    // -- layout 5
+    /// Exit fee tiers, sorted by `min_assets`, see [`Vault::active_exit_fee_tiers`]
+    pub exit_fee_tiers: [ExitFeeTier; EXIT_FEE_TIERS],
+    /// Holding period after which `exit_fee_held_bps` applies, zero disables it
+    pub exit_fee_holding_period: PodI64,
+    pub exit_fee_held_bps: PodU64,
+
+    // -- layout 6
+    /// Proposed next `admin`, the default key when no rotation is pending
+    pub pending_admin: Pubkey,
+    /// Proposed next `slash_admin`, the default key when no rotation is pending
+    pub pending_slash_admin: Pubkey,
+
+    // -- layout 7
+    /// Roles granted to other authorities, see [`Vault::has_role`]
+    pub roles: [RoleEntry; ROLE_ENTRIES],
+
+    // -- layout 8
+    /// [`VaultStatus`] gating the operations
+    pub status: u8,
 }
 
 impl Vault {
@@ -33,6 +238,51 @@ impl Vault {
         Vault::default()
     }
 
+    /// Current holder of `role`
+    pub fn authority(&self, role: AuthorityRole) -> &Pubkey {
+        match role {
+            AuthorityRole::Admin => &self.admin,
+            AuthorityRole::SlashAdmin => &self.slash_admin,
+        }
+    }
+
+    pub fn status(&self) -> VaultResult<VaultStatus> {
+        VaultStatus::try_from(self.status).map_err(|_| VaultError::InvalidLayout)
+    }
+
+    /// Fails unless the current status allows `action`
+    pub fn require_allows(&self, action: VaultAction) -> VaultResult<()> {
+        require!(self.status()?.allows(action), VaultError::VaultPaused);
+        Ok(())
+    }
+
+    /// Whether `authority` holds `role`, either as `admin` or `slash_admin`
+    /// or through the roles table
+    pub fn has_role(&self, authority: &Pubkey, role: Role) -> bool {
+        match role {
+            Role::Admin => authority == &self.admin,
+            _ if authority == &self.admin && ADMIN_DEFAULT_ROLES & role.mask() != 0 => true,
+            Role::Slasher if authority == &self.slash_admin => true,
+            _ => self
+                .roles
+                .iter()
+                .any(|entry| &entry.authority == authority && entry.roles & role.mask() != 0),
+        }
+    }
+
+    /// Key proposed for `role`, if a rotation is pending
+    pub fn pending_authority(&self, role: AuthorityRole) -> Option<&Pubkey> {
+        let pending = match role {
+            AuthorityRole::Admin => &self.pending_admin,
+            AuthorityRole::SlashAdmin => &self.pending_slash_admin,
+        };
+        (*pending != Pubkey::default()).then_some(pending)
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.discriminator == VAULT_DISCRIMINATOR
+    }
+
     pub fn num_shares(&self) -> u64 {
         self.shares.into()
     }
@@ -49,17 +299,156 @@ impl Vault {
         self.fee_amount = 0u64.into();
     }
 
+    /// Accrues a fee held in the vault assets account until it is collected
+    pub fn add_fee(&mut self, amt: u64) -> VaultResult<()> {
+        self.fee_amount = self
+            .fee_amount()
+            .checked_add(amt)
+            .ok_or(VaultError::MathOverflow)?
+            .into();
+        self.checked_gross_assets()?;
+        Ok(())
+    }
+
+    /// Assets held by the vault assets account: depositor assets and accrued fees
     pub fn gross_assets(&self) -> u64 {
         // -- guaranteed to not overflow
-        self.num_assets().checked_add(self.fee_amount()).unwrap()
+        self.checked_gross_assets().unwrap()
+    }
+
+    pub fn checked_gross_assets(&self) -> VaultResult<u64> {
+        self.num_assets()
+            .checked_add(self.fee_amount())
+            .ok_or(VaultError::MathOverflow)
     }
 
     pub fn fee_in_bps(&self) -> VaultResult<FeeBps> {
         u64::from(self.fee_bps).try_into()
     }
 
+    pub fn management_fee_in_bps(&self) -> VaultResult<FeeBps> {
+        u64::from(self.management_fee_bps).try_into()
+    }
+
+    pub fn last_fee_accrual_ts(&self) -> i64 {
+        self.last_fee_accrual_ts.into()
+    }
+
+    pub fn performance_fee_in_bps(&self) -> VaultResult<FeeBps> {
+        u64::from(self.performance_fee_bps).try_into()
+    }
+
+    /// Exit fee on a withdrawal of `assets`, held for `held_for` seconds if known
+    ///
+    /// The rate is the one of the highest tier reached by `assets`, lowered
+    /// to `exit_fee_held_bps` once the holding period is over.
+    pub fn exit_fee_in_bps(&self, assets: u64, held_for: Option<i64>) -> VaultResult<FeeBps> {
+        let tier_bps = self
+            .active_exit_fee_tiers()
+            .iter()
+            .take_while(|tier| u64::from(tier.min_assets) <= assets)
+            .last()
+            .map_or(0, |tier| u64::from(tier.fee_bps));
+
+        let holding_period = i64::from(self.exit_fee_holding_period);
+        let fee_bps = match held_for {
+            Some(held_for) if holding_period > 0 && held_for >= holding_period => {
+                tier_bps.min(self.exit_fee_held_bps.into())
+            }
+            _ => tier_bps,
+        };
+        fee_bps.try_into()
+    }
+
+    /// Exit fee tiers in use, an all-zero tier after the first one ends them
+    pub fn active_exit_fee_tiers(&self) -> &[ExitFeeTier] {
+        let unused = ExitFeeTier::default();
+        let len = self.exit_fee_tiers[1..]
+            .iter()
+            .position(|tier| *tier == unused)
+            .map_or(EXIT_FEE_TIERS, |idx| idx + 1);
+        &self.exit_fee_tiers[..len]
+    }
+
+    /// Exit fee tiers in use are sorted, the unused ones are zero, and every
+    /// rate is a valid fraction
+    pub fn validate_exit_fee(&self) -> VaultResult<()> {
+        let tiers = self.active_exit_fee_tiers();
+        let mut min_assets = 0;
+        for tier in tiers {
+            require_ge!(
+                u64::from(tier.min_assets),
+                min_assets,
+                VaultError::GuardFail
+            );
+            min_assets = tier.min_assets.into();
+            let _: FeeBps = u64::from(tier.fee_bps).try_into()?;
+        }
+        require!(
+            self.exit_fee_tiers[tiers.len()..]
+                .iter()
+                .all(|tier| *tier == ExitFeeTier::default()),
+            VaultError::GuardFail
+        );
+        let _: FeeBps = u64::from(self.exit_fee_held_bps).try_into()?;
+        Ok(())
+    }
+
+    /// Assets needed to price the current supply at the high-water mark, rounded up
+    pub fn high_water_mark_value(&self) -> VaultResult<u64> {
+        mul_div_ceil(
+            self.virtual_shares()?,
+            self.high_water_mark_assets.into(),
+            self.high_water_mark_shares.into(),
+        )
+    }
+
+    /// Sets the high-water mark to the current share price
+    pub fn set_high_water_mark(&mut self) {
+        self.high_water_mark_assets = self.num_assets().saturating_add(VIRTUAL_ASSETS).into();
+        self.high_water_mark_shares = self
+            .num_shares()
+            .saturating_add(self.virtual_shares_offset())
+            .into();
+    }
+
+    /// Raises the high-water mark if the current share price is above it
+    pub fn raise_high_water_mark(&mut self) -> VaultResult<()> {
+        let price =
+            u128::from(self.virtual_assets()?) * u128::from(u64::from(self.high_water_mark_shares));
+        let mark =
+            u128::from(u64::from(self.high_water_mark_assets)) * u128::from(self.virtual_shares()?);
+        if price > mark {
+            self.set_high_water_mark();
+        }
+        Ok(())
+    }
+
+    /// Virtual shares added to the supply when converting, `10^decimals_offset`
+    ///
+    /// Together with one virtual asset this makes inflating the share price
+    /// by donating assets cost the attacker `10^decimals_offset` times what
+    /// depositors lose to rounding.
+    pub fn virtual_shares_offset(&self) -> u64 {
+        10u64.pow(self.decimals_offset.into())
+    }
+
+    pub fn virtual_shares(&self) -> VaultResult<u64> {
+        self.num_shares()
+            .checked_add(self.virtual_shares_offset())
+            .ok_or(VaultError::MathOverflow)
+    }
+
+    pub fn virtual_assets(&self) -> VaultResult<u64> {
+        self.num_assets()
+            .checked_add(VIRTUAL_ASSETS)
+            .ok_or(VaultError::MathOverflow)
+    }
+
+    /// Every share is backed by at least `10^-decimals_offset` assets
     pub fn is_solvent(&self) -> bool {
-        self.num_shares() <= self.num_assets()
+        u128::from(self.num_shares())
+            <= u128::from(self.num_assets()) * u128::from(self.virtual_shares_offset())
     }
 
     pub fn check_invariant(&self) -> VaultResult<()> {
@@ -67,24 +456,95 @@ impl Vault {
     }
 
     pub fn convert_shares_to_assets(&self, shares: u64) -> VaultResult<u64> {
-        let assets = if self.num_shares() == self.num_assets() {
+        let (virtual_shares, virtual_assets) = (self.virtual_shares()?, self.virtual_assets()?);
+        let assets = if virtual_shares == virtual_assets {
             shares
         } else {
-            mul_div_floor(shares, self.num_assets(), self.num_shares())?
+            mul_div_floor(shares, virtual_assets, virtual_shares)?
         };
         Ok(assets)
     }
 
     pub fn convert_assets_to_shares(&self, token: u64) -> VaultResult<u64> {
-        let shares = if self.num_shares() == self.num_assets() {
+        let (virtual_shares, virtual_assets) = (self.virtual_shares()?, self.virtual_assets()?);
+        let shares = if virtual_shares == virtual_assets {
             token
         } else {
-            mul_div_floor(token, self.num_shares(), self.num_assets())?
+            mul_div_floor(token, virtual_shares, virtual_assets)?
         };
 
         Ok(shares)
     }
 
+    pub fn convert_shares_to_assets_ceil(&self, shares: u64) -> VaultResult<u64> {
+        let (virtual_shares, virtual_assets) = (self.virtual_shares()?, self.virtual_assets()?);
+        let assets = if virtual_shares == virtual_assets {
+            shares
+        } else {
+            mul_div_ceil(shares, virtual_assets, virtual_shares)?
+        };
+        Ok(assets)
+    }
+
+    pub fn convert_assets_to_shares_ceil(&self, token: u64) -> VaultResult<u64> {
+        let (virtual_shares, virtual_assets) = (self.virtual_shares()?, self.virtual_assets()?);
+        let shares = if virtual_shares == virtual_assets {
+            token
+        } else {
+            mul_div_ceil(token, virtual_shares, virtual_assets)?
+        };
+
+        Ok(shares)
+    }
+
+    /// Shares minted by a deposit of `assets`, net of the deposit fee
+    ///
+    /// Quotes `DepositWithFee`; equals a plain deposit when the fee is zero.
+    pub fn preview_deposit(&self, assets: u64) -> VaultResult<u64> {
+        let gross = self.fee_in_bps()?.apply(assets)?;
+        self.convert_assets_to_shares(gross.net_amount)
+    }
+
+    /// Assets paid to mint exactly `shares`, rounded up
+    pub fn preview_mint(&self, shares: u64) -> VaultResult<u64> {
+        self.convert_shares_to_assets_ceil(shares)
+    }
+
+    /// Shares burned to withdraw exactly `assets` plus the exit fee, rounded up
+    ///
+    /// Quotes the full tier rate, the holding period reduction is not known.
+    pub fn preview_withdraw(&self, assets: u64) -> VaultResult<u64> {
+        let gross = self.exit_fee_in_bps(assets, None)?.gross_up(assets)?;
+        self.convert_assets_to_shares_ceil(gross.total()?)
+    }
+
+    /// Assets paid out for redeeming `shares`, net of the exit fee, rounded down
+    ///
+    /// Quotes the full tier rate, the holding period reduction is not known.
+    pub fn preview_redeem(&self, shares: u64) -> VaultResult<u64> {
+        let assets = self.convert_shares_to_assets(shares)?;
+        Ok(self
+            .exit_fee_in_bps(assets, None)?
+            .apply(assets)?
+            .net_amount)
+    }
+
+    /// Maximum amount of assets the vault can still hold
+    pub fn max_deposit(&self) -> u64 {
+        if self.require_allows(VaultAction::Deposit).is_err() {
+            return 0;
+        }
+        u64::MAX - self.gross_assets()
+    }
+
+    /// Maximum amount of shares that an owner of `owner_shares` can redeem
+    pub fn max_redeem(&self, owner_shares: u64) -> u64 {
+        if self.require_allows(VaultAction::Redeem).is_err() {
+            return 0;
+        }
+        owner_shares.min(self.num_shares())
+    }
+
     pub fn burn_shares(&mut self, amt: u64) -> VaultResult<()> {
         self.shares = self
             .num_shares()
@@ -111,6 +571,7 @@ impl Vault {
             .checked_add(amt)
             .ok_or(VaultError::MathOverflow)?
             .into();
+        self.checked_gross_assets()?;
         Ok(())
     }
 
@@ -128,6 +589,12 @@ impl Vault {
 
     pub fn validate(&self) -> VaultResult<()> {
         require_ne!(self.assets_mint, self.shares_mint, VaultError::GuardFail);
+        require_le!(
+            self.decimals_offset,
+            MAX_DECIMALS_OFFSET,
+            VaultError::GuardFail
+        );
+        self.status()?;
         Ok(())
     }
 }
@@ -136,7 +603,7 @@ impl Vault {
 #[macro_export]
 macro_rules! vault_assets_account_seeds {
     ($vault_pk: expr) => {
-        &[b"assets", vault_pk.as_ref()]
+        &[b"assets", $vault_pk.as_ref()]
     };
 }
 
@@ -157,3 +624,224 @@ pub fn create_vault_assets_account_address(
         &crate::ID,
     )
 }
+
+/// Seeds for the PDA that is the mint authority of the vault shares
+#[macro_export]
+macro_rules! shares_mint_authority_seeds {
+    ($vault_pk: expr) => {
+        &[b"mint_authority", $vault_pk.as_ref()]
+    };
+}
+
+/// Seeds for the PDA shares mint authority with bump
+#[macro_export]
+macro_rules! shares_mint_authority_seeds_with_bump {
+    ( $vault_pk:expr, $bump:expr ) => {
+        &[b"mint_authority", $vault_pk.as_ref(), &[$bump]]
+    };
+}
+
+pub fn create_shares_mint_authority_address(
+    vault_pk: &Pubkey,
+    vault: &Vault,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        shares_mint_authority_seeds_with_bump!(vault_pk, vault.shares_mint_authority_bump),
+        &crate::ID,
+    )
+}
+
+/// Shares a user holds since `held_for` seconds, for the exit fee holding period
+#[derive(Default, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct UserHolding {
+    pub held_for: i64,
+    pub shares: u64,
+}
+
+/// Deposits of a user in a vault, used to reduce the exit fee after the
+/// holding period
+#[repr(C)]
+#[derive(Default, Pod, Copy, Clone, Zeroable)]
+pub struct UserPosition {
+    pub discriminator: [u8; 8],
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    /// Shares deposited through this position and not redeemed yet
+    pub shares: PodU64,
+    pub last_deposit_ts: PodI64,
+    pub bump: u8,
+}
+
+impl UserPosition {
+    pub fn is_initialized(&self) -> bool {
+        self.discriminator == USER_POSITION_DISCRIMINATOR
+    }
+
+    pub fn num_shares(&self) -> u64 {
+        self.shares.into()
+    }
+
+    /// Restarts the holding period for all shares of the position
+    pub fn record_deposit(&mut self, shares: u64, now: i64) -> VaultResult<()> {
+        self.shares = self
+            .num_shares()
+            .checked_add(shares)
+            .ok_or(VaultError::MathOverflow)?
+            .into();
+        self.last_deposit_ts = now.into();
+        Ok(())
+    }
+
+    pub fn record_withdrawal(&mut self, shares: u64) {
+        self.shares = self.num_shares().saturating_sub(shares).into();
+    }
+
+    /// Shares of the position and the time since the last deposit
+    pub fn holding(&self, now: i64) -> UserHolding {
+        UserHolding {
+            held_for: now.saturating_sub(self.last_deposit_ts.into()),
+            shares: self.num_shares(),
+        }
+    }
+}
+
+/// Seeds for the PDA user position of `$owner_pk` in a vault
+#[macro_export]
+macro_rules! user_position_seeds {
+    ( $vault_pk:expr, $owner_pk:expr ) => {
+        &[b"position", $vault_pk.as_ref(), $owner_pk.as_ref()]
+    };
+}
+
+/// Seeds for the PDA user position with bump
+#[macro_export]
+macro_rules! user_position_seeds_with_bump {
+    ( $vault_pk:expr, $owner_pk:expr, $bump:expr ) => {
+        &[
+            b"position",
+            $vault_pk.as_ref(),
+            $owner_pk.as_ref(),
+            &[$bump],
+        ]
+    };
+}
+
+pub fn create_user_position_address(
+    vault_pk: &Pubkey,
+    position: &UserPosition,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        user_position_seeds_with_bump!(vault_pk, position.owner, position.bump),
+        &crate::ID,
+    )
+}
+
+/// Vault parameters that can only be changed through a [`PendingChange`]
+#[repr(u8)]
+#[derive(TryFromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParamKind {
+    FeeBps = 0,
+    FeeTokenAccount = 1,
+    Admin = 2,
+    SlashAdmin = 3,
+}
+
+/// New value of a vault parameter
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParamChange {
+    FeeBps(u64),
+    FeeTokenAccount(Pubkey),
+    Admin(Pubkey),
+    SlashAdmin(Pubkey),
+}
+
+impl ParamChange {
+    /// Decodes a value stored as 32 bytes, fee rates are little endian
+    pub fn decode(kind: u8, value: &[u8; 32]) -> VaultResult<Self> {
+        let kind = ParamKind::try_from(kind).map_err(|_| VaultError::InvalidLayout)?;
+        let pubkey = Pubkey::new_from_array(*value);
+        Ok(match kind {
+            ParamKind::FeeBps => {
+                let mut bps = [0u8; 8];
+                bps.copy_from_slice(&value[..8]);
+                require!(
+                    value[8..].iter().all(|&b| b == 0),
+                    VaultError::InvalidLayout
+                );
+                Self::FeeBps(u64::from_le_bytes(bps))
+            }
+            ParamKind::FeeTokenAccount => Self::FeeTokenAccount(pubkey),
+            ParamKind::Admin => Self::Admin(pubkey),
+            ParamKind::SlashAdmin => Self::SlashAdmin(pubkey),
+        })
+    }
+
+    pub fn encode(&self) -> (ParamKind, [u8; 32]) {
+        match self {
+            Self::FeeBps(bps) => {
+                let mut value = [0u8; 32];
+                value[..8].copy_from_slice(&bps.to_le_bytes());
+                (ParamKind::FeeBps, value)
+            }
+            Self::FeeTokenAccount(pk) => (ParamKind::FeeTokenAccount, pk.to_bytes()),
+            Self::Admin(pk) => (ParamKind::Admin, pk.to_bytes()),
+            Self::SlashAdmin(pk) => (ParamKind::SlashAdmin, pk.to_bytes()),
+        }
+    }
+}
+
+/// Parameter change queued by the vault admin, executable by anyone once
+/// `execute_after` is reached
+#[repr(C)]
+#[derive(Default, Pod, Copy, Clone, Zeroable)]
+pub struct PendingChange {
+    pub discriminator: [u8; 8],
+    pub vault: Pubkey,
+    /// [`ParamKind`] of the change, also part of the PDA seeds
+    pub kind: u8,
+    pub value: [u8; 32],
+    pub execute_after: PodI64,
+    /// Receives the rent when the change is executed or cancelled
+    pub rent_receiver: Pubkey,
+    pub bump: u8,
+}
+
+impl PendingChange {
+    pub fn is_initialized(&self) -> bool {
+        self.discriminator == PENDING_CHANGE_DISCRIMINATOR
+    }
+
+    pub fn change(&self) -> VaultResult<ParamChange> {
+        ParamChange::decode(self.kind, &self.value)
+    }
+
+    pub fn is_ready(&self, now: i64) -> bool {
+        now >= i64::from(self.execute_after)
+    }
+}
+
+/// Seeds for the PDA pending change of a parameter of a vault
+#[macro_export]
+macro_rules! pending_change_seeds {
+    ( $vault_pk:expr, $kind:expr ) => {
+        &[b"pending_change", $vault_pk.as_ref(), &[$kind]]
+    };
+}
+
+/// Seeds for the PDA pending change with bump
+#[macro_export]
+macro_rules! pending_change_seeds_with_bump {
+    ( $vault_pk:expr, $kind:expr, $bump:expr ) => {
+        &[b"pending_change", $vault_pk.as_ref(), &[$kind], &[$bump]]
+    };
+}
+
+pub fn create_pending_change_address(
+    vault_pk: &Pubkey,
+    pending: &PendingChange,
+) -> Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        pending_change_seeds_with_bump!(vault_pk, pending.kind, pending.bump),
+        &crate::ID,
+    )
+}
@@ -17,10 +17,14 @@ pub mod math_native {
     }
 
     pub fn mul_div_ceil(a: u64, b: u64, c: u64) -> VaultResult<u64> {
+        let c = c as u128;
+        if c == 0 {
+            return Err(VaultError::MathOverflow);
+        }
         (a as u128)
             .checked_mul(b as u128)
             .ok_or(VaultError::MathOverflow)?
-            .div_ceil(c as u128)
+            .div_ceil(c)
             .try_into()
             .map_err(|_| VaultError::MathOverflow)
     }
@@ -69,7 +73,16 @@ pub struct GrossAmount {
     pub fee: u64,
 }
 
+impl GrossAmount {
+    pub fn total(&self) -> VaultResult<u64> {
+        self.net_amount
+            .checked_add(self.fee)
+            .ok_or(VaultError::MathOverflow)
+    }
+}
+
 const ONE_IN_BPS: u64 = 10_000u64;
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
 pub struct FeeBps(u64);
 
 impl TryFrom<u64> for FeeBps {
@@ -85,12 +98,37 @@ impl FeeBps {
         self.0 == 0
     }
 
+    pub fn bps(&self) -> u64 {
+        self.0
+    }
+
     pub fn apply(&self, amt: u64) -> VaultResult<GrossAmount> {
         let fee = mul_div_floor(amt, self.0, ONE_IN_BPS)?;
         let net_amount = amt.checked_sub(fee).ok_or(VaultError::MathOverflow)?;
         Ok(GrossAmount { net_amount, fee })
     }
 
+    /// Applies the fee as an annual rate, pro rata over `elapsed` seconds
+    ///
+    /// The fee never exceeds `amt`, no matter how long `elapsed` is.
+    pub fn apply_annual(&self, amt: u64, elapsed: u64) -> VaultResult<GrossAmount> {
+        let one_year = ONE_IN_BPS * SECONDS_PER_YEAR;
+        let rate = self.0.saturating_mul(elapsed).min(one_year);
+        let fee = mul_div_floor(amt, rate, one_year)?;
+        let net_amount = amt.checked_sub(fee).ok_or(VaultError::MathOverflow)?;
+        Ok(GrossAmount { net_amount, fee })
+    }
+
+    /// Adds the fee on top of `net_amount`, so that applying the fee to the
+    /// total leaves at least `net_amount`
+    pub fn gross_up(&self, net_amount: u64) -> VaultResult<GrossAmount> {
+        let total = mul_div_ceil(net_amount, ONE_IN_BPS, ONE_IN_BPS - self.0)?;
+        let fee = total
+            .checked_sub(net_amount)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(GrossAmount { net_amount, fee })
+    }
+
     pub fn apply_ceil(&self, amt: u64) -> VaultResult<GrossAmount> {
         let fee = mul_div_ceil(amt, self.0, ONE_IN_BPS)?;
         let net_amount = amt.checked_sub(fee).ok_or(VaultError::MathOverflow)?;